@@ -1,9 +1,10 @@
 use std::collections::{HashMap, HashSet};
 
 use super::*;
-use rand::seq::SliceRandom;
+use rand::{seq::SliceRandom, Rng};
+use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct Anagram {
     pub scramble: String,
 
@@ -12,10 +13,17 @@ pub struct Anagram {
 }
 
 impl Anagram {
-    pub fn new(list: &str, size: usize, params: &AnagramParams) -> Self {
+    /// Picks a random scramble from the `size`-letter words of `list` that has enough
+    /// words for `params`, if any does.
+    pub fn new(
+        list: &str,
+        size: usize,
+        params: &AnagramParams,
+        rng: &mut impl Rng,
+    ) -> Option<Self> {
         let mut scrambles = HashSet::new();
         for word in list.split_ascii_whitespace() {
-            if word.len() == size {
+            if letter_count(word) == size {
                 let mut scramble: Vec<char> = word.chars().collect();
                 scramble.sort();
                 let scramble = scramble.iter().fold("".to_string(), |mut s, c| {
@@ -26,21 +34,16 @@ impl Anagram {
             }
         }
 
-        let scrambles: Vec<String> = scrambles.into_iter().collect();
-        let mut scramble = scrambles
-            .choose(&mut rand::thread_rng())
-            .expect("Failed to pick Scramble")
-            .to_owned();
-        let mut words = Self::words_from_scramble(&scramble, list);
-
-        while !Self::valid_anagrams(&words, params) {
-            // println!("Invalid scramble");
-            scramble = scrambles
-                .choose(&mut rand::thread_rng())
-                .expect("Failed to pick Scramble")
-                .to_owned();
-            words = Self::words_from_scramble(&scramble, list);
-        }
+        // Sorted as a HashSet doesn't iterate in a stable order, which a seeded RNG needs.
+        let mut scrambles: Vec<String> = scrambles.into_iter().collect();
+        scrambles.sort_unstable();
+
+        // Tried in a random order, a custom word list might not have a valid one.
+        scrambles.shuffle(rng);
+        let (scramble, words) = scrambles.into_iter().find_map(|scramble| {
+            let words = Self::words_from_scramble(&scramble, list);
+            Self::valid_anagrams(&words, params).then_some((scramble, words))
+        })?;
 
         // This was used to count all possible anagrams from the parameters.
         // let mut anagrams = Vec::new();
@@ -59,15 +62,15 @@ impl Anagram {
         // dbg!(&anagrams.len());
         // todo!();
 
-        Anagram {
+        Some(Anagram {
             // Rescramble of the chars so they aren't in alphabetical (which was used to remove duplicates)
             scramble: scramble
                 .chars()
                 .collect::<Vec<_>>()
-                .choose_multiple(&mut rand::thread_rng(), scramble.len())
+                .choose_multiple(rng, letter_count(&scramble))
                 .collect(),
             words,
-        }
+        })
     }
 
     fn words_from_scramble(scramble: &str, input: &str) -> HashMap<usize, Vec<String>> {
@@ -100,7 +103,7 @@ impl Anagram {
             }
 
             if is_subset {
-                let entry = result.entry(word.len()).or_insert_with(Vec::new);
+                let entry = result.entry(letter_count(&word)).or_default();
                 entry.push(word);
             }
         }
@@ -116,20 +119,26 @@ impl Anagram {
         }
 
         for (letters, anagrams) in words.iter() {
-            if params.letter_range.contains(letters) {
-                if anagrams.len() < params.entry_min(*letters) {
-                    return false;
-                }
+            if params.letter_range.contains(letters) && anagrams.len() < params.entry_min(*letters)
+            {
+                return false;
             }
         }
         true
     }
 
-    pub fn valid_word(&self, word: &String) -> bool {
-        match self.words.get(&word.len()) {
-            Some(e) => e.contains(word),
-            None => false,
-        }
+    /// Whether `word` can be made from the letters of the scramble.
+    pub fn can_make(&self, word: &str) -> bool {
+        let mut letters: Vec<char> = self.scramble.to_lowercase().chars().collect();
+        word.to_lowercase()
+            .chars()
+            .all(|c| match letters.iter().position(|&l| l == c) {
+                Some(i) => {
+                    letters.swap_remove(i);
+                    true
+                }
+                None => false,
+            })
     }
 
     // THE BELOW ARE OBSOLETE METHODS OF GENERATING ANAGRAMS ARCHAICLY.
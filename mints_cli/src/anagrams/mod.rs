@@ -1,7 +1,7 @@
 use std::{
     collections::HashMap,
     error::Error,
-    io::{BufRead, BufReader, Write},
+    ops::RangeInclusive,
     thread::{self},
     time::Instant,
 };
@@ -10,13 +10,16 @@ use self::{anagram::Anagram, utils::AnagramParams};
 use colored::Colorize;
 use mints_lib::*;
 use rand::seq::SliceRandom;
+use rand_chacha::ChaCha12Rng;
+use serde::{Deserialize, Serialize};
 use terminal_size::terminal_size;
 
 mod anagram;
 mod utils;
 
-/// This is the size of the scramble the user will need to solve. This is also
-/// consequently the maximum size of words in the game.
+/// This is the size of the scramble the user will need to solve, unless set by
+/// `anagram_letters` in the config. This is also consequently the maximum size of
+/// words in the game.
 pub const ANAGRAM_SCRAMBLE_SIZE: usize = 8;
 
 /// This list contains more common words used as to avoid random guessing of words that
@@ -27,13 +30,13 @@ pub const ANAGRAM_WORD_LIST: &str = include_str!("./anagram_words.txt");
 /// This is the minimum terminal width required to play Anagrams (as a result of the big text).
 const MIN_TERM_WIDTH: usize = 75;
 
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Anagrams {
-    pub current_level: i32,
-    pub max_guesses: Option<i32>,
     pub current_guesses: i32,
     pub anagram: Option<Anagram>,
     pub answers: HashMap<usize, Vec<String>>,
+    pub history: Vec<Guess>,
+    #[serde(with = "mints_lib::started")]
     pub time_started: Instant,
 
     /// The difficulty in this case is not the words themselves, but the
@@ -42,11 +45,40 @@ pub struct Anagrams {
     pub difficulty: Difficulty,
 
     pub params: AnagramParams,
+
+    /// Picks the scramble and hints, seeded from `--seed` when given. Saved with
+    /// the round so a resumed one carries on drawing where it left off.
+    pub rng: ChaCha12Rng,
+
+    /// The daily puzzle number, when playing `--daily`.
+    pub puzzle: Option<i64>,
+
+    /// The guesses taken, curated by the words the scramble was picked for.
+    pub allowed: AllowedGuesses,
+
+    pub hints: Hints,
+
+    /// The word being hinted at, until it's found.
+    pub hint_word: Option<String>,
+
+    /// The (lowercase) words to make the scramble from.
+    #[serde(skip)]
+    pub list: String,
 }
 
 impl Game for Anagrams {
+    const GAME: Mints = Mints::Anagrams;
+
     fn new(args: &Args) -> Self {
-        let params = AnagramParams::new(&args.difficulty);
+        let params = AnagramParams::new(&args.difficulty, scramble_size());
+        let list = match &args.word_list {
+            WordList::Builtin(_) => ANAGRAM_WORD_LIST.to_string(),
+            WordList::Custom(path) => {
+                WordList::load_custom(path, Self::word_lengths(args), args.language)
+                    .map(|list| list.words.join("\n").to_lowercase())
+                    .unwrap_or_default()
+            }
+        };
         let mut answers = HashMap::new();
 
         for i in params.letter_range.clone() {
@@ -54,20 +86,41 @@ impl Game for Anagrams {
         }
 
         Anagrams {
-            current_level: 0,
-            max_guesses: None,
             current_guesses: 0,
             anagram: None,
             answers,
+            history: Vec::new(),
             time_started: Instant::now(),
             difficulty: args.difficulty.clone(),
             params,
+            rng: args.rng::<Self>(),
+            puzzle: args.puzzle_number(),
+            allowed: AllowedGuesses::new(
+                args.strictness().unwrap_or(Strictness::Strict),
+                args.language,
+                Vec::new(),
+            ),
+            hints: Hints::default(),
+            hint_word: None,
+            list,
         }
     }
 
-    fn start(&mut self) {
+    fn word_lengths(args: &Args) -> RangeInclusive<usize> {
+        let params = AnagramParams::new(&args.difficulty, scramble_size());
+        *params.letter_range.start()..=params.scramble_size
+    }
+
+    fn start(&mut self, io: &mut Console) {
+        // A resumed round already has its scramble.
+        if self.anagram.is_some() {
+            self.display_big_scramble(io, true, &config().theme.present);
+            self.display(io, None);
+            return;
+        }
+
         // let list = WORDS_MASTER.to_string();
-        let list = ANAGRAM_WORD_LIST.to_string();
+        let list = &self.list;
         // Uncomment to shadow and filter for only words defined by webster
         // let mut list = "".to_string();
         // for word in WORDS_MASTER.split_ascii_whitespace() {
@@ -76,14 +129,10 @@ impl Game for Anagrams {
         //     }
         // }
 
-        let term_width = if let Some((terminal_size::Width(w), _)) = terminal_size() {
-            w as usize
-        } else {
-            80 // Default width in case terminal size can't be determined
-        };
-
-        if term_width < MIN_TERM_WIDTH {
-            println!(
+        let term_width = term_width();
+        if term_width < MIN_TERM_WIDTH && !io.is_headless() {
+            outln!(
+                io,
                 "Increase your terminal size goof! (Curr: {term_width}, Min: {MIN_TERM_WIDTH})"
             );
             return;
@@ -92,125 +141,246 @@ impl Game for Anagrams {
         // Hopefully this spends less than 3s picking a scramble.
         thread::scope(|s| {
             s.spawn(|| {
-                self.anagram = Some(Anagram::new(&list, ANAGRAM_SCRAMBLE_SIZE, &self.params))
+                self.anagram =
+                    Anagram::new(list, self.params.scramble_size, &self.params, &mut self.rng)
             });
-            titled_loading_screen("ANAGRAMS", "white", 3000);
+            titled_loading_screen(io, "ANAGRAMS", "white", 3000);
         });
 
-        self.display_big_scramble(false, "yellow");
-        self.display(None);
+        let Some(anagram) = &self.anagram else {
+            outln!(
+                io,
+                "None of the words in the list make a scramble with enough words!"
+            );
+            return;
+        };
+        self.allowed = AllowedGuesses::new(
+            self.allowed.strictness,
+            self.allowed.language,
+            anagram.words.values().flatten().cloned(),
+        );
+
+        self.display_big_scramble(io, false, &config().theme.present);
+        self.display(io, None);
     }
 
-    fn do_loop(&mut self) -> Result<i32, Box<dyn Error>> {
+    fn do_loop(&mut self, io: &mut Console) -> Result<LoopOutcome, Box<dyn Error>> {
         if self.anagram.is_none() {
-            println!("Checking in 2s..");
-            sleep!(2000);
-            return Ok(GAME_RESTART);
+            // A small terminal can be resized, a word list without a scramble can't be fixed.
+            if term_width() >= MIN_TERM_WIDTH || io.is_headless() {
+                return Ok(LoopOutcome::Over(self.result(Outcome::Abandoned)));
+            }
+
+            outln!(io, "Checking in 2s..");
+            sleep!(io, 2000);
+            return Ok(LoopOutcome::Restart(self.result(Outcome::Abandoned)));
         }
 
-        let mut input = BufReader::new(std::io::stdin()).lines();
-        let guess = match input.next() {
-            Some(s) => s.unwrap().to_ascii_uppercase(),
-            None => return Ok(GAME_OVER),
+        let guess = match io.read_line()? {
+            Some(s) => self.allowed.language.normalise(&s),
+            None => return Ok(LoopOutcome::Over(self.result(Outcome::Abandoned))),
         };
 
         // Handle command and early return
         if guess.starts_with('!') {
-            return Ok(self.handle_commands(&guess.to_ascii_lowercase()));
+            return handle_command(self, io, &guess);
         }
 
-        if !guess.chars().all(|c| c.is_ascii_alphabetic()) {
-            println!("No special characters allowed silly!");
-        } else if !self.params.letter_range.contains(&guess.len()) {
-            println!("No {}-letter words needed goof!", guess.len());
+        if !self.allowed.language.is_word(&guess) {
+            outln!(io, "No special characters allowed silly!");
+        } else if !self.params.letter_range.contains(&letter_count(&guess)) {
+            outln!(io, "No {}-letter words needed goof!", letter_count(&guess));
         } else {
-            if !self
-                .anagram
-                .as_ref()
-                .unwrap()
-                .valid_word(&guess.to_ascii_lowercase())
-            {
-                self.display_big_scramble(true, "red");
-                self.display(Some(guess));
+            self.current_guesses += 1;
+
+            let anagram = self.anagram.as_ref().unwrap();
+            let feedback = if !anagram.can_make(&guess) || !self.allowed.allows(&guess) {
+                self.display_big_scramble(io, true, &config().theme.wrong);
+                self.display(io, Some(guess.clone()));
+                "invalid"
+            } else if !self.insert_entry(&guess) {
+                outln!(io, "No more {}-letter words needed", letter_count(&guess));
+                "full"
             } else {
-                if !self.insert_entry(&guess) {
-                    println!("No more {}-letter words needed", guess.len());
-                } else {
-                    self.display_big_scramble(true, "green");
-                    self.display(None);
-                }
-            }
+                self.display_big_scramble(io, true, &config().theme.correct);
+                self.display(io, None);
+                "valid"
+            };
+            self.history.push(Guess {
+                guess,
+                feedback: feedback.to_string(),
+            });
         }
 
         if self.check_win() {
-            newln!();
-            println!("You won!");
-            println!("Play again? (y/n)");
-
-            return match input.next() {
-                Some(s) => match s.unwrap().as_str() {
-                    "Y" | "y" => Ok(GAME_RESTART),
-                    _ => Ok(GAME_OVER),
-                },
-                None => Ok(GAME_OVER),
-            };
+            newln!(io);
+            outln!(io, "You won!");
+
+            return Ok(play_again(io, self.result(Outcome::Won))?);
+        }
+
+        Ok(LoopOutcome::Ongoing)
+    }
+
+    fn result(&self, outcome: Outcome) -> GameResult {
+        GameResult {
+            game: Self::GAME,
+            letters: self.params.scramble_size as i32,
+            difficulty: self.difficulty.clone(),
+            outcome,
+            answer: self
+                .anagram
+                .as_ref()
+                .map(|a| a.scramble.to_uppercase())
+                .unwrap_or_default(),
+            guesses: self.current_guesses,
+            history: self.history.clone(),
+            hints_used: self.hints.used,
+            assisted: false,
+            score: self.score(outcome),
+            puzzle: self.puzzle,
+            elapsed: self.time_started.elapsed(),
+        }
+    }
+
+    const RESTART_COUNTDOWN: u64 = 5;
+
+    fn commands(&self) -> Commands {
+        Commands::default().register("shuffle", &["s"], "Shuffle the letters of the scramble")
+    }
+
+    fn hint(&mut self, io: &mut Console) {
+        // Hints keep building on the same word until it's found.
+        if let Some(word) = self.hint_word.clone().filter(|word| !self.answered(word)) {
+            self.hints.give(io, &word, &[]);
+            return;
+        }
+
+        // Most functional thing I've ever written and it's so ugly I love it.
+        // All this does is picks a random word from the hashmap of words formable
+        // from the Anagram excluding ones already answered.
+        let mut words: Vec<String> = self
+            .get_words()
+            .into_iter()
+            .fold(Vec::new(), |mut list, (letters, mut sub)| {
+                if self.params.letter_range.contains(&letters) {
+                    list.append(&mut sub);
+                }
+                list
+            })
+            .into_iter()
+            .filter(|word| !self.answered(word) && define(word).is_some())
+            .collect();
+        // The hashmap doesn't iterate in a stable order, sort for seeded hints.
+        words.sort_unstable();
+
+        let Some(word) = words.choose(&mut self.rng).cloned() else {
+            outln!(io, "No hints for what's left!");
+            return;
+        };
+
+        self.hints.next_word();
+        self.hints.give(io, &word, &[]);
+        self.hint_word = Some(word);
+    }
+
+    fn reveal(&mut self, io: &mut Console) {
+        self.populate_answers();
+        self.display_big_scramble(io, false, &config().theme.wrong);
+        self.display(io, None);
+        outln!(io, "Here's what you missed!");
+    }
+
+    fn run_command(&mut self, io: &mut Console, name: &str) -> Result<LoopOutcome, Box<dyn Error>> {
+        if name == "shuffle" {
+            if let Some(anagram) = self.anagram.as_mut() {
+                let mut letters: Vec<char> = anagram.scramble.chars().collect();
+                letters.shuffle(&mut self.rng);
+                anagram.scramble = letters.into_iter().collect();
+            }
+            self.display_big_scramble(io, true, &config().theme.present);
+            self.display(io, None);
         }
 
-        Ok(GAME_ONGOING)
+        Ok(LoopOutcome::Ongoing)
     }
 
-    fn finish(mut self) {
-        clear!();
+    fn finish(mut self, io: &mut Console) {
+        // Nothing to show if a scramble was never picked.
+        if self.anagram.is_none() {
+            return;
+        }
+
+        clear!(io);
         self.populate_answers();
-        self.display_big_scramble(false, "red");
-        self.display(None);
-        println!("Here's what you missed!");
+        self.display_big_scramble(io, false, &config().theme.wrong);
+        self.display(io, None);
+        outln!(io, "Here's what you missed!");
 
         drop(self);
     }
 }
 
+/// The size of the scramble, from the config or `ANAGRAM_SCRAMBLE_SIZE`.
+fn scramble_size() -> usize {
+    config().anagram_letters.unwrap_or(ANAGRAM_SCRAMBLE_SIZE)
+}
+
+fn term_width() -> usize {
+    if let Some((terminal_size::Width(w), _)) = terminal_size() {
+        w as usize
+    } else {
+        80 // Default width in case terminal size can't be determined
+    }
+}
+
 impl Anagrams {
-    fn display_big_scramble(&self, skip_animation: bool, color: &str) {
-        let scramble = self.get_scramble().to_ascii_uppercase();
-        clear!();
+    fn display_big_scramble(&self, io: &mut Console, skip_animation: bool, color: &str) {
+        let scramble = self.get_scramble().to_uppercase();
+        clear!(io);
 
         if !skip_animation {
-            for (i, _) in scramble.chars().enumerate() {
-                print!("{}", terminal_fonts::to_block_string(&scramble[0..=i]));
-                newln!();
-                flush!();
-
-                if i == scramble.len() - 1 {
-                    sleep!(500);
+            for (i, c) in scramble.char_indices() {
+                let end = i + c.len_utf8();
+                out!(io, "{}", terminal_fonts::to_block_string(&scramble[..end]));
+                newln!(io);
+                flush!(io);
+
+                if end == scramble.len() {
+                    sleep!(io, 500);
                 } else {
-                    sleep!(100);
+                    sleep!(io, 100);
                 }
 
-                clear!();
-                newln!();
+                clear!(io);
+                newln!(io);
             }
         }
 
-        print!(
+        out!(
+            io,
             "{}",
             terminal_fonts::to_block_string(scramble.as_str()).color(color)
         );
-        newln!(3);
+        newln!(io, 3);
     }
 
-    fn display(&self, wrong: Option<String>) {
-        let sections: Vec<usize> = self.params.letter_range.clone().into_iter().collect();
+    fn display(&self, io: &mut Console, wrong: Option<String>) {
+        let sections: Vec<usize> = self.params.letter_range.clone().collect();
+
+        if let Some(puzzle) = self.puzzle {
+            outln!(io, "{}", format!(" Puzzle #{puzzle} ").bold());
+            newln!(io);
+        }
 
         for section in sections {
-            self.print_section(section, &wrong);
+            self.print_section(io, section, &wrong);
         }
     }
 
-    fn print_section(&self, section: usize, wrong: &Option<String>) {
+    fn print_section(&self, io: &mut Console, section: usize, wrong: &Option<String>) {
         let section_width = terminal_fonts::to_block_string(self.get_scramble().as_str())
             .split_terminator('\n')
-            .into_iter()
             .next()
             .unwrap()
             .len()
@@ -221,19 +391,26 @@ impl Anagrams {
         let padding_length = (section_width - header.len()) / 2;
         let padding = "=".repeat(padding_length);
 
-        println!("{}", "=".repeat(section_width));
-        println!("{}{}{}", padding, header.bold(), padding);
+        outln!(io, "{}", "=".repeat(section_width));
+        outln!(io, "{}{}{}", padding, header.bold(), padding);
 
         let row_count = self.params.entry_min(section);
         for row in 0..row_count {
-            self.print_row(row, section_width, section, wrong);
+            self.print_row(io, row, section_width, section, wrong);
         }
 
-        println!("{}", "=".repeat(section_width));
-        newln!(2);
+        outln!(io, "{}", "=".repeat(section_width));
+        newln!(io, 2);
     }
 
-    fn print_row(&self, row: usize, width: usize, section: usize, wrong: &Option<String>) {
+    fn print_row(
+        &self,
+        io: &mut Console,
+        row: usize,
+        width: usize,
+        section: usize,
+        wrong: &Option<String>,
+    ) {
         let placeholder = format!(" {}", "_ ".repeat(section));
         let padding_width = ((width - placeholder.len()) / 2) - 2;
         let left = format!("{}>>", "=".repeat(padding_width));
@@ -242,49 +419,84 @@ impl Anagrams {
         let section_answers = self.answers.get(&section).unwrap();
         match section_answers.get(row) {
             Some(answer) => {
-                print!("{} ", left);
+                out!(io, "{} ", left);
                 for c in answer.chars() {
-                    print!("{} ", c.to_ascii_uppercase().to_string().green());
-                    flush!();
+                    out!(
+                        io,
+                        "{} ",
+                        c.to_uppercase()
+                            .to_string()
+                            .color(config().theme.correct.as_str())
+                    );
+                    flush!(io);
 
                     if section_answers.get(row + 1).is_none() {
-                        sleep!(100);
+                        sleep!(io, 100);
                     }
                 }
-                println!("{}", right);
+                outln!(io, "{}", right);
             }
             None => {
                 if let Some(guess) = wrong {
-                    if section == guess.len()
-                        && (section_answers.get(row - 1).is_some()
+                    if section == letter_count(guess)
+                        && ((row > 0 && section_answers.get(row - 1).is_some())
                             || (section_answers.get(row).is_none() && row == 0))
                     {
-                        print!("{} ", left);
+                        out!(io, "{} ", left);
                         for c in guess.chars() {
-                            print!("{} ", c.to_ascii_uppercase().to_string().red());
-                            flush!();
-                            sleep!(50);
+                            out!(
+                                io,
+                                "{} ",
+                                c.to_uppercase()
+                                    .to_string()
+                                    .color(config().theme.wrong.as_str())
+                            );
+                            flush!(io);
+                            sleep!(io, 50);
                         }
-                        print!("{}", right);
-                        sleep!(500);
+                        out!(io, "{}", right);
+                        sleep!(io, 500);
                     }
                 }
 
-                println!("\r\x1B[K{}{}{}", left, placeholder, right);
-                flush!();
+                outln!(io, "\r\x1B[K{}{}{}", left, placeholder, right);
+                flush!(io);
             }
         }
     }
 
-    fn insert_entry(&mut self, guess: &String) -> bool {
-        let entry_limit = self.params.entry_min(guess.len());
-        let entry_count = self.answers.get(&guess.len()).unwrap().len();
+    /// Every entry found counts, won or not, with a bonus for a quick win. There
+    /// are no guesses to run out of, so no bonus for those.
+    fn score(&self, outcome: Outcome) -> Score {
+        let found: Vec<&String> = self.answers.values().flatten().collect();
+        let score = Score::new(&found, &self.difficulty, self.hints.used);
+        match outcome {
+            Outcome::Won => score.in_time(self.time_started.elapsed()),
+            _ => score,
+        }
+    }
+
+    /// Whether the word is one of the entries already found, in any case.
+    fn answered(&self, word: &str) -> bool {
+        self.answers
+            .get(&letter_count(word))
+            .is_some_and(|answers| {
+                answers
+                    .iter()
+                    .any(|a| a.to_lowercase() == word.to_lowercase())
+            })
+    }
+
+    fn insert_entry(&mut self, guess: &str) -> bool {
+        let letters = letter_count(guess);
+        let entry_limit = self.params.entry_min(letters);
+        let entry_count = self.answers.get(&letters).unwrap().len();
 
         let res = entry_count < entry_limit;
 
         if res {
-            self.answers.entry(guess.len()).and_modify(|e| {
-                if !e.contains(guess) {
+            self.answers.entry(letters).and_modify(|e| {
+                if !e.iter().any(|w| w == guess) {
                     e.push(guess.to_owned());
                 }
             });
@@ -326,52 +538,4 @@ impl Anagrams {
     fn get_words(&self) -> HashMap<usize, Vec<String>> {
         self.anagram.clone().expect("Anagram not chosen!").words
     }
-
-    fn handle_commands(&mut self, cmd: &String) -> i32 {
-        match cmd.as_str() {
-            "!hint" | "!h" => {
-                // Most functional thing I've ever written and it's so ugly I love it.
-                // All this does is picks a random word from the hashmap of words formable
-                // from the Anagram excluding ones already answered.
-                let word: String = self
-                    .get_words()
-                    .into_iter()
-                    .fold(Vec::new(), |mut list, (letters, mut sub)| {
-                        if self.params.letter_range.contains(&letters) {
-                            list.append(&mut sub);
-                        }
-                        list
-                    })
-                    .into_iter()
-                    .filter(|word| {
-                        !self.answers.get(&word.len()).unwrap().contains(word)
-                            && webster::dictionary(word).is_some()
-                    })
-                    .collect::<Vec<String>>()
-                    .choose(&mut rand::thread_rng())
-                    .expect("Failed to choose random hint!")
-                    .to_owned();
-
-                println!("Hint: {}", hint(&word));
-                GAME_ONGOING
-            }
-            "!restart" | "!next" | "!reset" | "!r" => {
-                self.populate_answers();
-                self.display_big_scramble(false, "red");
-                self.display(None);
-                println!("Here's what you missed!");
-
-                print!("Restarting in 5.. ");
-                flush!();
-                sleep!(5000);
-
-                GAME_RESTART
-            }
-            "!quit" | "!leave" | "!exit" | "!q" => GAME_OVER,
-            _ => {
-                println!("Unknown command!");
-                GAME_ONGOING
-            }
-        }
-    }
 }
@@ -1,16 +1,18 @@
 use std::{collections::HashMap, ops::RangeInclusive};
 
 use mints_lib::Difficulty;
+use serde::{Deserialize, Serialize};
 
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct AnagramParams {
+    pub scramble_size: usize,
     pub letter_range: RangeInclusive<usize>,
     pub difficulty: Difficulty,
     entry_amounts: HashMap<usize, (usize, usize, usize)>,
 }
 
 impl AnagramParams {
-    pub fn new(diff: &Difficulty) -> Self {
+    pub fn new(diff: &Difficulty, scramble_size: usize) -> Self {
         let mut map = HashMap::new();
 
         let range = match diff {
@@ -18,6 +20,8 @@ impl AnagramParams {
             Difficulty::Medium => 5..=7,
             Difficulty::Hard => 5..=8,
         };
+        // Words can't be any longer than the scramble they're made from.
+        let range = *range.start()..=scramble_size.min(*range.end());
 
         for i in range.clone() {
             let insert = match i {
@@ -32,6 +36,7 @@ impl AnagramParams {
         }
 
         AnagramParams {
+            scramble_size,
             letter_range: range,
             difficulty: diff.clone(),
             entry_amounts: map,
@@ -1,15 +1,12 @@
-use std::{
-    collections::HashSet,
-    error::Error,
-    io::{BufRead, BufReader, Write},
-    time::{Duration, Instant},
-};
+use std::{collections::HashSet, error::Error, ops::RangeInclusive, time::Instant};
 
 use colored::Colorize;
 use terminal_size::{terminal_size, Width};
 
 use mints_lib::*;
+use serde::{Deserialize, Serialize};
 
+/// The length of the words, unless set by `hangman_letters` in the config.
 const HANGMAN_WORD_SIZE: i32 = 7;
 const HANGMAN_GUESS_SIZE: i32 = 7;
 
@@ -23,111 +20,179 @@ const ASCII_6: &str = include_str!("./ascii/6.txt");
 const ASCII_WIN: &str = include_str!("./ascii/win.txt");
 const ASCII_LOSE: &str = include_str!("./ascii/lose.txt");
 
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Hangman {
     pub turn: i32,
     pub correct: Vec<char>,
     pub incorrect: Vec<char>,
+    pub history: Vec<Guess>,
     pub answer: String,
+    #[serde(with = "mints_lib::started")]
     pub time_started: Instant,
     pub difficulty: Difficulty,
+    pub language: Language,
+    pub hints: Hints,
+    #[serde(skip, default = "ascii")]
     pub ascii: Vec<String>,
+
+    /// The daily puzzle number, when playing `--daily`.
+    pub puzzle: Option<i64>,
+}
+
+/// The length of the words, from the config or `HANGMAN_WORD_SIZE`.
+fn word_size() -> i32 {
+    config().hangman_letters.unwrap_or(HANGMAN_WORD_SIZE)
+}
+
+/// The gallows for each wrong guess.
+fn ascii() -> Vec<String> {
+    [
+        ASCII_0, ASCII_1, ASCII_2, ASCII_3, ASCII_4, ASCII_5, ASCII_6,
+    ]
+    .iter()
+    .map(|s| s.to_string())
+    .collect()
 }
 
 impl Game for Hangman {
+    const GAME: Mints = Mints::Hangman;
+
     fn new(args: &Args) -> Self {
-        let words = load_word_list(HANGMAN_WORD_SIZE, &args.difficulty);
-        let answer = choose_random_word(&words);
+        let answer = AnswerPool::new(word_size(), args).choose(&mut args.rng::<Self>());
 
         Hangman {
             turn: 0,
             correct: Vec::new(),
             incorrect: Vec::new(),
+            history: Vec::new(),
             answer,
             time_started: std::time::Instant::now(),
             difficulty: args.difficulty.clone(),
-            ascii: vec![
-                ASCII_0, ASCII_1, ASCII_2, ASCII_3, ASCII_4, ASCII_5, ASCII_6,
-            ]
-            .iter()
-            .map(|s| s.to_string())
-            .collect(),
+            language: args.language,
+            hints: Hints::default(),
+            ascii: ascii(),
+            puzzle: args.puzzle_number(),
         }
     }
 
-    fn start(&mut self) {
-        self.display(None);
+    fn word_lengths(_args: &Args) -> RangeInclusive<usize> {
+        word_size() as usize..=word_size() as usize
+    }
+
+    fn start(&mut self, io: &mut Console) {
+        self.display(io, None);
         // println!("Answer: {}", self.answer);
         // println!("Hint: {}", hint(&self.answer));
     }
 
-    fn do_loop(&mut self) -> Result<i32, Box<dyn Error>> {
-        let mut input = BufReader::new(std::io::stdin()).lines();
-        let guess = match input.next() {
-            Some(s) => s.unwrap().to_ascii_uppercase(),
-            None => return Ok(GAME_OVER),
+    fn do_loop(&mut self, io: &mut Console) -> Result<LoopOutcome, Box<dyn Error>> {
+        let guess = match io.read_line()? {
+            Some(s) => self.language.normalise(&s),
+            None => return Ok(LoopOutcome::Over(self.result(Outcome::Abandoned))),
         };
-        let letter = guess.chars().nth(0).unwrap_or(';');
+        let letter = guess.chars().next().unwrap_or(';');
 
         // Handle command and early return
         if guess.starts_with('!') {
-            return Ok(self.handle_commands(&guess.to_ascii_lowercase()));
+            return handle_command(self, io, &guess);
         }
 
         // Correct amount of letters
-        if guess.len() != 1 {
-            println!("Guess one letter at a time!");
+        if letter_count(&guess) != 1 {
+            outln!(io, "Guess one letter at a time!");
 
-        // Is a ascii alphabetical
-        } else if !letter.is_ascii_alphabetic() {
-            println!("{letter} is not a letter silly!");
+        // Is in the language's alphabet
+        } else if !self.language.is_letter(letter) {
+            outln!(io, "{letter} is not a letter silly!");
 
         // Already guessed
         } else if self.incorrect.contains(&letter) || self.correct.contains(&letter) {
-            println!("You've guesses '{letter}' already!");
+            outln!(io, "You've guesses '{letter}' already!");
 
         // Otherwise go to next turn
         } else {
-            if self.answer.contains(letter) {
+            let feedback = if self.answer.contains(letter) {
                 self.correct.push(letter);
+                "hit"
             } else {
                 self.incorrect.push(letter);
-            }
-
-            if self.check_win() {
-                self.display(Some(true));
+                "miss"
+            };
+            self.history.push(Guess {
+                guess: letter.to_string(),
+                feedback: feedback.to_string(),
+            });
+
+            let outcome = if self.check_win() {
+                self.display(io, Some(true));
+                Outcome::Won
             } else if self.incorrect.len() == HANGMAN_GUESS_SIZE as usize {
-                self.display(Some(false));
+                self.display(io, Some(false));
+                Outcome::Lost
             } else {
                 self.turn += 1;
-                self.display(None);
-                return Ok(GAME_ONGOING);
-            }
-
-            println!("Play again? (y/n)");
-            return match input.next() {
-                Some(s) => match s.unwrap().as_str() {
-                    "Y" | "y" => Ok(GAME_RESTART),
-                    _ => Ok(GAME_OVER),
-                },
-                None => Ok(GAME_OVER),
+                self.display(io, None);
+                return Ok(LoopOutcome::Ongoing);
             };
+
+            return Ok(play_again(io, self.result(outcome))?);
         }
 
-        Ok(GAME_ONGOING)
+        Ok(LoopOutcome::Ongoing)
+    }
+
+    fn result(&self, outcome: Outcome) -> GameResult {
+        GameResult {
+            game: Self::GAME,
+            letters: letter_count(&self.answer) as i32,
+            difficulty: self.difficulty.clone(),
+            outcome,
+            answer: self.answer.clone(),
+            guesses: self.history.len() as i32,
+            history: self.history.clone(),
+            hints_used: self.hints.used,
+            assisted: false,
+            score: self.score(outcome),
+            puzzle: self.puzzle,
+            elapsed: self.time_started.elapsed(),
+        }
     }
 
-    fn finish(self) {
-        println!("The word was {}!", self.answer);
-        drop(self);
+    fn hint(&mut self, io: &mut Console) {
+        let known: Vec<bool> = self
+            .answer
+            .chars()
+            .map(|c| self.correct.contains(&c))
+            .collect();
+        self.hints.give(io, &self.answer, &known);
+    }
+
+    fn reveal(&mut self, io: &mut Console) {
+        outln!(io, "The word was {}!", self.answer);
+    }
+
+    fn finish(mut self, io: &mut Console) {
+        self.reveal(io);
     }
 }
 
 impl Hangman {
-    fn display(&self, win: Option<bool>) {
+    /// The answer's letters when won, with a bonus for each wrong guess left
+    /// and for a quick win.
+    fn score(&self, outcome: Outcome) -> Score {
+        match outcome {
+            Outcome::Won => Score::new(&[&self.answer], &self.difficulty, self.hints.used)
+                .guesses_left((HANGMAN_GUESS_SIZE as usize - self.incorrect.len()) as u32)
+                .in_time(self.time_started.elapsed()),
+            _ => Score::new::<&str>(&[], &self.difficulty, self.hints.used),
+        }
+    }
+
+    fn display(&self, io: &mut Console, win: Option<bool>) {
+        let theme = &config().theme;
         // Clear terminal
-        print!("{}[2J", 27 as char);
-        newln!();
+        clear!(io);
+        newln!(io);
 
         let term_width = if let Some((Width(w), _)) = terminal_size() {
             w as usize
@@ -135,60 +200,60 @@ impl Hangman {
             80 // Default width in case terminal size can't be determined
         };
 
-        let header_text = match self.turn {
-            0 => " Hangman! ".to_string(),
-            _ => {
-                if win.is_none() {
-                    format!(" Round {} ", self.turn + 1)
-                } else if win.unwrap() {
-                    " You won! ".to_string()
-                } else {
-                    format!(" The word was {}! ", self.answer)
-                }
-            }
+        let header_text = match (self.turn, win) {
+            (0, _) => " Hangman! ".to_string(),
+            (_, None) => format!(" Round {} ", self.turn + 1),
+            (_, Some(true)) => " You won! ".to_string(),
+            (_, Some(false)) => format!(" The word was {}! ", self.answer),
+        };
+        let header_text = match self.puzzle {
+            Some(puzzle) => format!(" Puzzle #{puzzle} -{header_text}"),
+            None => header_text,
         };
 
         // Calculate padding
-        let text_width = header_text.len() + (HANGMAN_WORD_SIZE as usize * 2); // Adding 10 for the side indicators ("== ", " ==")
-        let padding_length = term_width / 2 - text_width / 2;
+        let text_width = letter_count(&header_text) + (letter_count(&self.answer) * 2); // Adding 10 for the side indicators ("== ", " ==")
+        let padding_length = (term_width / 2).saturating_sub(text_width / 2);
         let padding = "=".repeat(padding_length);
 
         // Print header with padding
-        println!("{}{}{}", padding, header_text.bold(), padding);
-        newln!();
+        outln!(io, "{}{}{}", padding, header_text.bold(), padding);
+        newln!(io);
 
         // Print ascii art
-        if win.is_none() {
-            print!("{}", self.ascii[self.incorrect.len()]);
-        } else if win.unwrap() {
-            print!("{}", ASCII_WIN);
-        } else {
-            print!("{}", ASCII_LOSE);
+        match win {
+            None => out!(io, "{}", self.ascii[self.incorrect.len()]),
+            Some(true) => out!(io, "{}", ASCII_WIN),
+            Some(false) => out!(io, "{}", ASCII_LOSE),
         }
-        newln!(2);
+        newln!(io, 2);
 
-        let bar = "=".repeat((padding_length * 2) + header_text.len());
-        println!("{bar}");
-        newln!();
+        let bar = "=".repeat((padding_length * 2) + letter_count(&header_text));
+        outln!(io, "{bar}");
+        newln!(io);
 
         // Print correct words
         for c in self.answer.chars() {
             if self.correct.contains(&c) {
-                print!(" {} ", c.to_string().green().bold());
+                out!(
+                    io,
+                    " {} ",
+                    c.to_string().color(theme.correct.as_str()).bold()
+                );
             } else {
-                print!(" {} ", "_".to_string().bold());
+                out!(io, " {} ", "_".to_string().bold());
             }
         }
 
-        newln!(2);
-        println!("{bar}");
-        newln!();
+        newln!(io, 2);
+        outln!(io, "{bar}");
+        newln!(io);
 
         for c in &self.incorrect {
-            print!(" {} ", c.to_string().red().bold());
+            out!(io, " {} ", c.to_string().color(theme.wrong.as_str()).bold());
         }
 
-        newln!(2);
+        newln!(io, 2);
     }
 
     fn check_win(&self) -> bool {
@@ -197,33 +262,4 @@ impl Hangman {
 
         answer == correct
     }
-
-    fn handle_commands(&mut self, cmd: &String) -> i32 {
-        match cmd.as_str() {
-            "!hint" | "!h" => {
-                println!("Hint: {}", hint(&self.answer));
-                GAME_ONGOING
-            }
-            "!restart" | "!next" | "!reset" | "!r" => {
-                println!("The word was {}!", self.answer);
-
-                print!("Restarting in 3.. ");
-                std::io::stdout().flush().expect("Failed to flush stdout");
-                std::thread::sleep(Duration::from_secs(1));
-                print!("2.. ");
-                std::io::stdout().flush().expect("Failed to flush stdout");
-                std::thread::sleep(Duration::from_secs(1));
-                print!("1.. ");
-                std::io::stdout().flush().expect("Failed to flush stdout");
-                std::thread::sleep(Duration::from_secs(1));
-
-                GAME_RESTART
-            }
-            "!quit" | "!leave" | "!exit" | "!q" => GAME_OVER,
-            _ => {
-                println!("Unknown command!");
-                GAME_ONGOING
-            }
-        }
-    }
 }
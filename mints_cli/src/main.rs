@@ -1,5 +1,9 @@
-use clap::Parser;
+use std::error::Error;
+
 use mints_lib::*;
+use serde::{de::DeserializeOwned, Serialize};
+
+mod menu;
 
 // Games:
 mod anagrams;
@@ -7,38 +11,228 @@ mod hangman;
 mod wordle;
 
 fn main() {
-    let args = Args::parse();
+    match Config::load() {
+        Ok(config) => config.install(),
+        Err(e) => {
+            eprintln!("Config Error: {e}");
+            std::process::exit(1);
+        }
+    }
+    let mut args = Args::parse_with(config());
+
+    let mut io = if args.headless {
+        colored::control::set_override(false);
+        Console::headless()
+    } else {
+        Console::stdio()
+    };
+
+    match args.action {
+        Some(Action::Stats) => {
+            println!("{}", Stats::load());
+            return;
+        }
+        Some(Action::Leaderboard { game, daily }) => {
+            leaderboard(game, daily);
+            return;
+        }
+        None => (),
+    }
+
+    let res = match args.game {
+        Some(_) => play(&mut io, args),
+        None => {
+            titled_loading_screen(&mut io, "MINTS", &config().theme.title, 2000);
+            launcher(&mut io, &mut args)
+        }
+    };
+
+    if let Err(e) = res {
+        eprintln!("Game Error: {e}");
+        std::process::exit(1);
+    }
+}
+
+/// Keeps showing the menu, playing whichever game is picked, until the player
+/// quits it.
+fn launcher(io: &mut Console, args: &mut Args) -> Result<(), Box<dyn Error>> {
+    while menu::choose(io, args)? {
+        if let Err(e) = play(io, args.clone()) {
+            outln!(io, "Game Error: {e}");
+        }
+        menu::pause(io)?;
+    }
+
+    Ok(())
+}
+
+/// Plays rounds of the game in `args` until the player stops.
+fn play(io: &mut Console, mut args: Args) -> Result<(), Box<dyn Error>> {
+    let lengths = match args.game {
+        Some(Mints::Wordle) => wordle::Wordle::word_lengths(&args),
+        Some(Mints::Hangman) => hangman::Hangman::word_lengths(&args),
+        Some(Mints::Anagrams) => anagrams::Anagrams::word_lengths(&args),
+        None => return Ok(()),
+    };
+    args.word_list.check(io, lengths, args.language)?;
 
     while match args.game {
-        Mints::Wordle => run_game(wordle::Wordle::new(&args)),
-        Mints::Hangman => run_game(hangman::Hangman::new(&args)),
-        Mints::Anagrams => run_game(anagrams::Anagrams::new(&args)),
-    } {}
+        Some(Mints::Wordle) => run_game::<wordle::Wordle>(io, &mut args)?,
+        Some(Mints::Hangman) => run_game::<hangman::Hangman>(io, &mut args)?,
+        Some(Mints::Anagrams) => run_game::<anagrams::Anagrams>(io, &mut args)?,
+        None => false,
+    } {
+        // There's only the one daily puzzle, so replaying it would just repeat it.
+        if args.daily {
+            outln!(io, "That's today's puzzle done, come back tomorrow!");
+            break;
+        }
+        args.next_round();
+    }
+
+    Ok(())
 }
 
-fn run_game<G: Game>(mut game: G) -> bool {
-    game.start();
+/// Starts a new round of `G`, or picks the saved one back up for `--resume`
+/// (along with the rules it was played by). Only the first round is resumed,
+/// the ones after it are new, carrying on from its seed.
+fn new_game<G: Game + DeserializeOwned>(io: &mut Console, args: &mut Args) -> (G, Rules) {
+    if std::mem::take(&mut args.resume) {
+        match load_game::<G>() {
+            Some(Save { rules, game }) => {
+                // A daily puzzle's seed is its own, not one to carry on from.
+                if game.result(Outcome::Abandoned).puzzle.is_none() {
+                    args.seed = rules.seed;
+                }
+                return (game, rules);
+            }
+            None => {
+                outln!(io, "There's no saved game to resume, starting a new one!");
+                sleep!(io, 2000);
+            }
+        }
+    }
 
-    let mut code;
-    loop {
-        code = match game.do_loop() {
-            Ok(code) => code,
+    (G::new(args), Rules::new::<G>(args))
+}
+
+/// Plays a single round of `G`, returning whether another round should be
+/// started afterwards.
+fn run_game<G: Game + Serialize + DeserializeOwned>(
+    io: &mut Console,
+    args: &mut Args,
+) -> Result<bool, Box<dyn Error>> {
+    let (mut game, rules): (G, Rules) = new_game(io, args);
+    game.start(io);
+
+    let result = loop {
+        match game.do_loop(io) {
+            Ok(LoopOutcome::Ongoing) => {
+                autosave(io, &game, &rules);
+                continue;
+            }
+            Ok(LoopOutcome::Restart(result)) => {
+                report(io, &result, &rules);
+                finished::<G>(io, &result);
+                return Ok(true);
+            }
+            Ok(LoopOutcome::Over(result)) => break result,
             Err(e) => {
-                eprintln!("Game Error: {e}");
-                1
+                if !autosave(io, &game, &rules) {
+                    game.finish(io);
+                }
+                return Err(e);
             }
-        };
+        }
+    };
 
-        match code {
-            GAME_ONGOING => continue,
-            GAME_OVER => break,
-            GAME_RESTART => {
-                return true;
-            }
-            _ => break,
+    // A saved round is only recorded once it's been resumed and finished, and
+    // keeps its answer hidden until then.
+    match result.outcome {
+        Outcome::Abandoned if autosave(io, &game, &rules) => {
+            outln!(io, "Saved! Pick it back up with --resume.");
+        }
+        _ => {
+            report(io, &result, &rules);
+            finished::<G>(io, &result);
+            game.finish(io);
+        }
+    }
+    Ok(false)
+}
+
+/// Records the round in the player's stats and on the leaderboard, or prints its
+/// result as a line of JSON when headless. Rounds abandoned before a single
+/// guess (i.e. the input running out) are left out of both.
+fn report(io: &mut Console, result: &GameResult, rules: &Rules) {
+    if !result.was_played() {
+        return;
+    }
+
+    if io.is_headless() {
+        println!(
+            "{}",
+            serde_json::to_string(result).expect("Failed to serialise result")
+        );
+        return;
+    }
+
+    let mut stats = Stats::load();
+    stats.record(result);
+    if let Err(e) = stats.save() {
+        eprintln!("Couldn't save your stats: {e}");
+    }
+
+    let mut leaderboard = Leaderboard::load();
+    if let Some(place) = leaderboard.record(result, rules) {
+        outln!(io, "That's #{place} on the leaderboard!");
+        if let Err(e) = leaderboard.save() {
+            eprintln!("Couldn't save the leaderboard: {e}");
+        }
+    }
+}
+
+/// Prints the leaderboard's boards for `mints leaderboard`.
+fn leaderboard(game: Option<Mints>, daily: bool) {
+    let leaderboard = Leaderboard::load();
+    let boards = leaderboard.boards(game, daily);
+    if boards.is_empty() {
+        println!("No scores on the leaderboard yet!");
+    }
+
+    for (i, board) in boards.iter().enumerate() {
+        if i > 0 {
+            println!();
         }
+        print!("{board}");
+    }
+}
+
+/// Saves the round as it stands, so quitting (or the terminal closing) doesn't
+/// lose it. Rounds without a single guess yet would only replace an older save.
+/// Returns whether the round was saved.
+fn autosave<G: Game + Serialize>(io: &Console, game: &G, rules: &Rules) -> bool {
+    if io.is_headless() || !game.result(Outcome::Abandoned).was_played() {
+        return false;
     }
 
-    game.finish();
-    std::process::exit(code);
+    match save_game(game, rules) {
+        Ok(()) => true,
+        Err(e) => {
+            eprintln!("Couldn't save the game: {e}");
+            false
+        }
+    }
+}
+
+/// Removes the save once its round is over. A restart before a single guess
+/// leaves it be.
+fn finished<G: Game>(io: &Console, result: &GameResult) {
+    if io.is_headless() || !result.was_played() {
+        return;
+    }
+
+    if let Err(e) = clear_save::<G>() {
+        eprintln!("Couldn't remove the saved game: {e}");
+    }
 }
@@ -0,0 +1,114 @@
+use std::{io, ops::RangeInclusive};
+
+use clap::ValueEnum;
+use colored::Colorize;
+use mints_lib::*;
+
+/// The launcher shown when `mints` is run without a game. Lets the player pick a
+/// game and its options, storing them in `args`. Returns `false` once the player
+/// quits (or the input runs out).
+pub fn choose(io: &mut Console, args: &mut Args) -> io::Result<bool> {
+    clear!(io);
+    newln!(io);
+    out!(
+        io,
+        "{}",
+        terminal_fonts::to_block_string("MINTS").color(config().theme.title.as_str())
+    );
+    newln!(io, 2);
+
+    for (i, game) in Mints::value_variants().iter().enumerate() {
+        outln!(io, "  {}) {:?}", i + 1, game);
+    }
+    outln!(io, "  q) Quit");
+    newln!(io);
+
+    let game = loop {
+        let Some(choice) = prompt(io, "Pick a game")? else {
+            return Ok(false);
+        };
+
+        if matches!(choice.as_str(), "q" | "quit") {
+            return Ok(false);
+        }
+
+        let game = match choice.parse::<usize>() {
+            Ok(i) => i
+                .checked_sub(1)
+                .and_then(|i| Mints::value_variants().get(i).cloned()),
+            Err(_) => Mints::from_str(&choice, true).ok(),
+        };
+
+        match game {
+            Some(game) => break game,
+            None => outln!(io, "{choice} isn't a game silly!"),
+        }
+    };
+
+    // Hangman and Anagrams have fixed letters and guesses, only Wordle can change them.
+    if let Mints::Wordle = game {
+        args.letters = ask_number(io, "Letters", args.letters, MIN_LETTERS..=MAX_LETTERS)?;
+        args.guesses = ask_number(io, "Guesses", args.guesses, 3..=9)?;
+    }
+    args.difficulty = ask_difficulty(io, &args.difficulty)?;
+    args.game = Some(game);
+
+    Ok(true)
+}
+
+/// Asks the player to hit enter before the menu is redrawn, so the end of the last
+/// game isn't cleared straight away.
+pub fn pause(io: &mut Console) -> io::Result<()> {
+    prompt(io, "Press enter to return to the menu")?;
+    Ok(())
+}
+
+/// Prints the question and reads back a trimmed, lowercase answer.
+fn prompt(io: &mut Console, question: &str) -> io::Result<Option<String>> {
+    out!(io, "{}: ", question.bold());
+    flush!(io);
+
+    Ok(io.read_line()?.map(|s| s.trim().to_ascii_lowercase()))
+}
+
+/// Asks for a number within `range`, keeping `current` if nothing is entered.
+fn ask_number(
+    io: &mut Console,
+    name: &str,
+    current: i32,
+    range: RangeInclusive<i32>,
+) -> io::Result<i32> {
+    let question = format!("{name} ({}-{}) [{current}]", range.start(), range.end());
+
+    loop {
+        match prompt(io, &question)? {
+            None => return Ok(current),
+            Some(s) if s.is_empty() => return Ok(current),
+            Some(s) => match s.parse() {
+                Ok(n) if range.contains(&n) => return Ok(n),
+                _ => outln!(
+                    io,
+                    "{s} isn't between {} and {}!",
+                    range.start(),
+                    range.end()
+                ),
+            },
+        }
+    }
+}
+
+/// Asks for a difficulty, keeping `current` if nothing is entered.
+fn ask_difficulty(io: &mut Console, current: &Difficulty) -> io::Result<Difficulty> {
+    let question = format!("Difficulty (easy/medium/hard) [{current:?}]");
+
+    loop {
+        match prompt(io, &question)? {
+            None => return Ok(current.clone()),
+            Some(s) if s.is_empty() => return Ok(current.clone()),
+            Some(s) => match Difficulty::from_str(&s, true) {
+                Ok(difficulty) => return Ok(difficulty),
+                Err(_) => outln!(io, "{s} isn't a difficulty!"),
+            },
+        }
+    }
+}
@@ -1,7 +1,6 @@
-use std::{collections::HashMap, io::Write, time::Duration};
-
 use crate::wordle::Wordle;
 use colored::Colorize;
+use mints_lib::*;
 use terminal_size::{terminal_size, Width};
 
 pub struct Display;
@@ -14,10 +13,10 @@ pub enum DisplayType {
 }
 
 impl Display {
-    pub fn display(display: DisplayType, game: &Wordle) {
+    pub fn display(io: &mut Console, display: &DisplayType, game: &Wordle) {
         // Clear terminal
-        print!("{}[2J", 27 as char);
-        println!();
+        clear!(io);
+        newln!(io);
 
         let term_width = if let Some((Width(w), _)) = terminal_size() {
             w as usize
@@ -31,33 +30,67 @@ impl Display {
             DisplayType::Victory => " You Won! ".to_string(),
             DisplayType::Failure => format!(" The word was {}! ", game.answer),
         };
+        let header_text = match game.puzzle {
+            Some(puzzle) => format!(" Puzzle #{puzzle} -{header_text}"),
+            None => header_text,
+        };
 
         // Calculate padding
-        let text_width = header_text.len() + (game.max_letters as usize * 2); // Adding 10 for the side indicators ("== ", " ==")
-        let padding_length = term_width / 2 - text_width / 2;
+        let text_width = letter_count(&header_text) + (game.max_letters as usize * 2); // Adding 10 for the side indicators ("== ", " ==")
+        let padding_length = (term_width / 2).saturating_sub(text_width / 2);
         let padding = "=".repeat(padding_length);
 
         // Print header with padding
-        println!("{}{}{}", padding, header_text.bold(), padding);
+        outln!(io, "{}{}{}", padding, header_text.bold(), padding);
 
         for index in 0..game.max_guesses {
-            Self::print_row(game, index);
+            Self::print_row(io, game, index);
         }
 
-        println!();
+        newln!(io);
+        Self::print_keyboard(io, game, term_width);
+        newln!(io);
+    }
+
+    /// The keyboard under the board, each letter colored by the best that's
+    /// known about it so far. Letters known not to be in the word are dimmed.
+    fn print_keyboard(io: &mut Console, game: &Wordle, term_width: usize) {
+        let theme = &config().theme;
+        let statuses = game.letter_statuses();
+
+        for row in game.allowed.language.keyboard() {
+            let width = letter_count(row) * 3;
+            out!(io, "{}", " ".repeat(term_width.saturating_sub(width) / 2));
+
+            for c in row.chars() {
+                let key = format!(" {c} ");
+                match statuses.get(&c) {
+                    Some(LetterResult::Correct) => {
+                        out!(io, "{}", key.color(theme.correct.as_str()).bold())
+                    }
+                    Some(LetterResult::Present) => {
+                        out!(io, "{}", key.color(theme.present.as_str()).bold())
+                    }
+                    Some(LetterResult::Absent) => out!(io, "{}", key.dimmed()),
+                    None => out!(io, "{key}"),
+                }
+            }
+            newln!(io);
+        }
     }
 
-    fn print_row(game: &Wordle, index: i32) {
+    fn print_row(io: &mut Console, game: &Wordle, index: i32) {
+        let theme = &config().theme;
         let term_width = if let Some((Width(w), _)) = terminal_size() {
             w as usize
         } else {
             80 // Default width in case terminal size can't be determined
         };
 
-        let padding_length = term_width / 2 - game.max_letters as usize * 3;
+        let padding_length = (term_width / 2).saturating_sub(game.max_letters as usize * 3);
         let padding = "=".repeat(padding_length);
 
-        print!("{}", padding);
+        out!(io, "{}", padding);
 
         let sides = if index == game.turn {
             (">> ", " <<")
@@ -65,54 +98,35 @@ impl Display {
             ("== ", " ==")
         };
 
-        print!("{}", sides.0);
+        out!(io, "{}", sides.0);
 
-        if let Some(guess) = game
-            .guesses
-            .get(&index)
-            .expect("Guess does not exist at turn index")
+        if let (Some(Some(guess)), Some(results)) =
+            (game.guesses.get(&index), game.results.get(&index))
         {
-            let mut answer_map = HashMap::new();
-            // First, count all letters in the answer
-            for c in game.answer.chars() {
-                *answer_map.entry(c).or_insert(0) += 1;
-            }
-
-            // First pass: Check for correct position (green)
-            let guess_chars: Vec<char> = guess.chars().collect();
-            let mut correctness = vec![false; guess.len()];
-
-            for (i, &c) in guess_chars.iter().enumerate() {
-                if game.answer.chars().nth(i) == Some(c) {
-                    correctness[i] = true;
-                    *answer_map.get_mut(&c).unwrap() -= 1;
-                }
-            }
-
-            // Second pass: Check for correct letters in wrong positions (yellow)
-            for (i, &c) in guess_chars.iter().enumerate() {
-                if correctness[i] {
-                    print!(" {} ", c.to_string().green());
-                } else if answer_map.get(&c).unwrap_or(&0) > &0 {
-                    print!(" {} ", c.to_string().yellow());
-                    *answer_map.get_mut(&c).unwrap() -= 1;
-                } else {
-                    print!(" {} ", c.to_string());
+            for (c, result) in guess.chars().zip(results) {
+                match result {
+                    LetterResult::Correct => {
+                        out!(io, " {} ", c.to_string().color(theme.correct.as_str()))
+                    }
+                    LetterResult::Present => {
+                        out!(io, " {} ", c.to_string().color(theme.present.as_str()))
+                    }
+                    LetterResult::Absent => out!(io, " {} ", c),
                 }
 
                 if index == game.turn - 1 {
-                    std::thread::sleep(Duration::from_millis(300));
-                    std::io::stdout().flush().expect("Failed to flush stdout");
+                    sleep!(io, 300);
+                    flush!(io);
                 }
             }
         } else {
             for _ in 0..game.max_letters {
-                print!(" _ ");
+                out!(io, " _ ");
             }
         }
 
-        print!("{}", sides.1);
-        print!("{}", padding);
-        println!();
+        out!(io, "{}", sides.1);
+        out!(io, "{}", padding);
+        newln!(io);
     }
 }
@@ -1,30 +1,49 @@
+use colored::Colorize;
 use mints_lib::*;
+use serde::{Deserialize, Serialize};
 
-use std::{
-    collections::HashMap,
-    error::Error,
-    io::{BufRead, BufReader, Write},
-    time::{Duration, Instant},
-};
+use std::{collections::HashMap, error::Error, ops::RangeInclusive, time::Instant};
 
 mod display;
 use crate::wordle::display::*;
 
-#[derive(Clone)]
+/// How many guesses `!suggest` recommends.
+const SUGGESTIONS: usize = 5;
+/// The most possible answers `!suggest` lists by name.
+const LISTED_CANDIDATES: usize = 10;
+
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Wordle {
     pub turn: i32,
-    pub words: Vec<String>,
+    pub allowed: AllowedGuesses,
     pub guesses: HashMap<i32, Option<String>>,
+    /// How each guess (by turn) compared to the answer, see `score_guess`.
+    pub results: HashMap<i32, Vec<LetterResult>>,
     pub answer: String,
+    /// The answers the round's answer was picked from, for the solver.
+    pub pool: AnswerPool,
+    #[serde(with = "mints_lib::started")]
     pub time_started: Instant,
     pub max_guesses: i32,
     pub max_letters: i32,
     pub difficulty: Difficulty,
+    pub hints: Hints,
+    pub hard_mode: bool,
+    /// Run the solver after every guess.
+    pub assist: bool,
+    /// Whether the solver has been used this round.
+    pub assisted: bool,
+
+    /// The daily puzzle number, when playing `--daily`.
+    pub puzzle: Option<i64>,
 }
 
 impl Game for Wordle {
+    const GAME: Mints = Mints::Wordle;
+
     fn new(args: &Args) -> Self {
-        let words = load_word_list(args.letters, &args.difficulty);
+        let pool = AnswerPool::new(args.letters, args);
+        let strictness = args.strictness().unwrap_or(Strictness::Standard);
 
         let mut map: HashMap<i32, Option<String>> = HashMap::new();
         for i in 0..args.guesses {
@@ -33,41 +52,64 @@ impl Game for Wordle {
 
         Wordle {
             turn: 0,
-            answer: choose_random_word(&words),
+            answer: pool.choose(&mut args.rng::<Self>()),
+            pool,
             guesses: map,
-            words,
+            results: HashMap::new(),
+            allowed: AllowedGuesses::for_list(strictness, args.letters, args),
             time_started: std::time::Instant::now(),
             max_guesses: args.guesses,
             max_letters: args.letters,
             difficulty: args.difficulty.clone(),
+            hints: Hints::default(),
+            hard_mode: args.hard_mode,
+            assist: args.assist,
+            assisted: false,
+            puzzle: args.puzzle_number(),
         }
     }
 
-    fn start(&mut self) {
-        Display::display(DisplayType::Start, &self);
-        // println!("Answer: {}", self.answer);
-        // println!("Hint: {}", hint(&self.answer));
+    fn word_lengths(args: &Args) -> RangeInclusive<usize> {
+        args.letters as usize..=args.letters as usize
+    }
+
+    fn start(&mut self, io: &mut Console) {
+        // A resumed round goes straight back to its board.
+        let display = match self.turn {
+            0 => DisplayType::Start,
+            _ => DisplayType::GameBoard,
+        };
+        Display::display(io, &display, self);
+        if self.assist {
+            self.suggest(io);
+        }
     }
 
-    fn do_loop(&mut self) -> Result<i32, Box<dyn Error>> {
-        let mut input = BufReader::new(std::io::stdin()).lines();
-        let guess = match input.next() {
-            Some(s) => s.unwrap().to_ascii_uppercase(),
-            None => return Ok(GAME_OVER),
+    fn do_loop(&mut self, io: &mut Console) -> Result<LoopOutcome, Box<dyn Error>> {
+        let guess = match io.read_line()? {
+            Some(s) => self.allowed.language.normalise(&s),
+            None => return Ok(LoopOutcome::Over(self.result(Outcome::Abandoned))),
         };
 
         // Handle command and early return
         if guess.starts_with('!') {
-            return Ok(self.handle_commands(&guess.to_ascii_lowercase()));
+            return handle_command(self, io, &guess);
         }
 
         // Correct amount of letters
-        if guess.len() != self.max_letters as usize {
-            println!("{guess} is not a {}-letter word!", self.max_letters);
+        if letter_count(&guess) != self.max_letters as usize {
+            outln!(io, "{guess} is not a {}-letter word!", self.max_letters);
 
         // Is an actual word
-        } else if !word_exists(self.max_letters, &guess) {
-            println!("{} is not a word silly!", guess);
+        } else if !self.allowed.allows(&guess) {
+            match self.allowed.strictness {
+                Strictness::Strict => outln!(io, "{guess} isn't on the word list!"),
+                _ => outln!(io, "{} is not a word silly!", guess),
+            }
+
+        // Doesn't use what's been revealed
+        } else if let Some(reason) = self.hard_mode_violation(&guess) {
+            outln!(io, "Hard mode: {reason}");
 
         // Already guessed
         } else if self
@@ -75,36 +117,233 @@ impl Game for Wordle {
             .values()
             .any(|g| g.as_ref().is_some_and(|s| s.contains(&guess)))
         {
-            println!("You've guessed {guess} already!");
+            outln!(io, "You've guessed {guess} already!");
 
         // Otherwise register guess
         } else {
-            Display::display(self.guess(&guess), &self);
-            if self.turn == self.max_guesses || self.answer.contains(&guess) {
-                println!("Play again? (y/n)");
-
-                return match input.next() {
-                    Some(s) => match s.unwrap().as_str() {
-                        "Y" | "y" => Ok(GAME_RESTART),
-                        _ => Ok(GAME_OVER),
-                    },
-                    None => Ok(GAME_OVER),
-                };
-            }
+            let display = self.guess(&guess);
+            Display::display(io, &display, self);
+
+            let outcome = match display {
+                DisplayType::Victory => Outcome::Won,
+                DisplayType::Failure => Outcome::Lost,
+                _ => {
+                    if self.assist {
+                        self.suggest(io);
+                    }
+                    return Ok(LoopOutcome::Ongoing);
+                }
+            };
+
+            self.analyse(io);
+            return Ok(play_again(io, self.result(outcome))?);
+        }
+
+        Ok(LoopOutcome::Ongoing)
+    }
+
+    fn result(&self, outcome: Outcome) -> GameResult {
+        GameResult {
+            game: Self::GAME,
+            letters: self.max_letters,
+            difficulty: self.difficulty.clone(),
+            outcome,
+            answer: self.answer.clone(),
+            guesses: self.turn,
+            history: self
+                .scored()
+                .map(|(guess, results)| Guess {
+                    guess: guess.clone(),
+                    feedback: marks(results),
+                })
+                .collect(),
+            hints_used: self.hints.used,
+            assisted: self.assisted,
+            score: self.score(outcome),
+            puzzle: self.puzzle,
+            elapsed: self.time_started.elapsed(),
+        }
+    }
+
+    fn hint(&mut self, io: &mut Console) {
+        // Letters already in the right spot don't need giving away.
+        let known: Vec<bool> = (0..self.max_letters as usize)
+            .map(|i| {
+                self.results
+                    .values()
+                    .any(|results| results.get(i) == Some(&LetterResult::Correct))
+            })
+            .collect();
+        self.hints.give(io, &self.answer, &known);
+    }
+
+    fn commands(&self) -> Commands {
+        Commands::default().register(
+            "suggest",
+            &["solve", "s"],
+            "List the answers still possible and the best next guesses",
+        )
+    }
+
+    fn run_command(&mut self, io: &mut Console, name: &str) -> Result<LoopOutcome, Box<dyn Error>> {
+        if name == "suggest" {
+            self.suggest(io);
         }
 
-        Ok(GAME_ONGOING)
+        Ok(LoopOutcome::Ongoing)
     }
 
-    fn finish(self) {
-        println!("The word was {}!", self.answer);
-        drop(self);
+    fn reveal(&mut self, io: &mut Console) {
+        outln!(io, "The word was {}!", self.answer);
+    }
+
+    fn finish(mut self, io: &mut Console) {
+        self.reveal(io);
     }
 }
 
 impl Wordle {
-    fn guess(&mut self, guess: &String) -> DisplayType {
-        self.guesses.insert(self.turn, Some(guess.clone()));
+    /// The answer's letters when won, with a bonus for each guess left over and
+    /// for a quick win.
+    fn score(&self, outcome: Outcome) -> Score {
+        match outcome {
+            Outcome::Won => Score::new(&[&self.answer], &self.difficulty, self.hints.used)
+                .guesses_left((self.max_guesses - self.turn) as u32)
+                .in_time(self.time_started.elapsed()),
+            _ => Score::new::<&str>(&[], &self.difficulty, self.hints.used),
+        }
+    }
+
+    /// The best that's known about each letter guessed so far, found in the
+    /// right spot beating found elsewhere beating not in the word.
+    pub fn letter_statuses(&self) -> HashMap<char, LetterResult> {
+        let rank = |result: &LetterResult| match result {
+            LetterResult::Correct => 2,
+            LetterResult::Present => 1,
+            LetterResult::Absent => 0,
+        };
+
+        let mut statuses = HashMap::new();
+        for (guess, results) in self.scored() {
+            for (c, result) in guess.chars().zip(results) {
+                let status = statuses.entry(c).or_insert(*result);
+                if rank(result) > rank(status) {
+                    *status = *result;
+                }
+            }
+        }
+        statuses
+    }
+
+    /// Lists the answers that still fit the feedback so far, then the guesses
+    /// expected to narrow them down the most (in bits, each halving what's left
+    /// on average). Guesses come from the curated list, and in hard mode only
+    /// the ones it allows. Marks the round as assisted.
+    fn suggest(&mut self, io: &mut Console) {
+        self.assisted = true;
+
+        let solver = self.solver();
+        let candidates = solver.candidates(self.scored());
+
+        match candidates.len() {
+            0 => {
+                outln!(
+                    io,
+                    "Nothing on the list fits, the answer's from somewhere else!"
+                );
+                return;
+            }
+            1 => {
+                outln!(io, "It has to be {}!", candidates[0]);
+                return;
+            }
+            n => outln!(
+                io,
+                "{n} possible answers left ({:.1} bits to go)",
+                (n as f64).log2()
+            ),
+        }
+        if candidates.len() <= LISTED_CANDIDATES {
+            outln!(io, "  {}", candidates.join(", "));
+        }
+
+        let suggestions = solver.suggest(&candidates, SUGGESTIONS, |guess| {
+            self.hard_mode_violation(guess).is_none()
+        });
+        outln!(io, "Best guesses:");
+        for suggestion in suggestions {
+            outln!(
+                io,
+                "  {}  {:.2} bits{}",
+                suggestion.word,
+                suggestion.entropy,
+                match suggestion.candidate {
+                    true => "  (could be it)",
+                    false => "",
+                }
+            );
+        }
+    }
+
+    /// Goes back over every guess once the round's over. Shows how many answers
+    /// were possible before and after it, its skill (the information it was
+    /// expected to give against the best guess there was) and its luck (how
+    /// much more or less it actually gave away), and whether it kept to hard
+    /// mode.
+    fn analyse(&self, io: &mut Console) {
+        let solver = self.solver();
+        let scored: Vec<(&String, &Vec<LetterResult>)> = self.scored().collect();
+
+        newln!(io);
+        outln!(io, "{}", " Guess by guess ".bold());
+        for (turn, (guess, _)) in scored.iter().enumerate() {
+            let previous = || scored[..turn].iter().copied();
+            let before = solver.candidates(previous());
+            let after = solver.candidates(scored[..=turn].iter().copied());
+            if after.is_empty() {
+                outln!(io, "  {}. {guess}  The answer isn't on the list", turn + 1);
+                continue;
+            }
+
+            let expected = information(guess, &before);
+            let best = solver
+                .suggest(&before, 1, |g| {
+                    !self.hard_mode || hard_mode_violation(previous(), g).is_none()
+                })
+                .first()
+                .map_or(expected, |best| best.entropy.max(expected));
+            let luck = (before.len() as f64 / after.len() as f64).log2() - expected;
+            let left = format!("{} -> {} left", before.len(), after.len());
+
+            outln!(
+                io,
+                "  {}. {guess}  {left:<18}Skill {expected:.1} of {best:.1} bits  Luck {luck:+.1} bits",
+                turn + 1
+            );
+            if let Some(reason) = hard_mode_violation(previous(), guess) {
+                outln!(io, "{}", format!("     Not hard mode: {reason}").dimmed());
+            }
+        }
+    }
+
+    /// A solver for the round's answers, guessing from the curated list.
+    fn solver(&self) -> Solver {
+        let guesses: Vec<&String> = self.allowed.curated().collect();
+        Solver::new(self.pool.words(), &guesses)
+    }
+
+    /// The guesses so far in the order they were made, with their results.
+    pub fn scored(&self) -> impl Iterator<Item = (&String, &Vec<LetterResult>)> {
+        (0..self.turn).filter_map(|turn| {
+            let guess = self.guesses.get(&turn)?.as_ref()?;
+            Some((guess, self.results.get(&turn)?))
+        })
+    }
+
+    fn guess(&mut self, guess: &str) -> DisplayType {
+        self.guesses.insert(self.turn, Some(guess.to_string()));
+        self.results
+            .insert(self.turn, score_guess(&self.answer, guess));
         self.turn += 1;
 
         if self.answer.contains(guess) {
@@ -116,32 +355,13 @@ impl Wordle {
         }
     }
 
-    fn handle_commands(&mut self, cmd: &String) -> i32 {
-        match cmd.as_str() {
-            "!hint" | "!h" => {
-                println!("Hint: {}", hint(&self.answer));
-                GAME_ONGOING
-            }
-            "!restart" | "!next" | "!reset" | "!r" => {
-                println!("The word was {}!", self.answer);
-
-                print!("Restarting in 3.. ");
-                std::io::stdout().flush().expect("Failed to flush stdout");
-                std::thread::sleep(Duration::from_secs(1));
-                print!("2.. ");
-                std::io::stdout().flush().expect("Failed to flush stdout");
-                std::thread::sleep(Duration::from_secs(1));
-                print!("1.. ");
-                std::io::stdout().flush().expect("Failed to flush stdout");
-                std::thread::sleep(Duration::from_secs(1));
-
-                GAME_RESTART
-            }
-            "!quit" | "!leave" | "!exit" | "!q" => GAME_OVER,
-            _ => {
-                println!("Unknown command!");
-                GAME_ONGOING
-            }
+    /// Checks a guess against everything the previous ones revealed when playing
+    /// hard mode, see `mints_lib::hard_mode_violation`.
+    fn hard_mode_violation(&self, guess: &str) -> Option<String> {
+        if !self.hard_mode {
+            return None;
         }
+
+        hard_mode_violation(self.scored(), guess)
     }
 }
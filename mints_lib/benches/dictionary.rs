@@ -0,0 +1,53 @@
+//! Times guess validation through `AllowedGuesses::allows` for each
+//! `Strictness`. Run with `cargo bench`.
+//!
+//! The first `Standard` lookup builds the shared `Dictionary`, every one after
+//! that should be well under a millisecond.
+
+use std::{hint::black_box, time::Instant};
+
+use clap::ValueEnum;
+use mints_lib::{load_word_list, AllowedGuesses, Difficulty, Language, Strictness, WordList};
+
+const ROUNDS: u32 = 100;
+
+fn main() {
+    // Curated by the answers, as the games do.
+    let curated: Vec<String> = (4..=8)
+        .flat_map(|letters| {
+            load_word_list(
+                letters,
+                &Difficulty::Hard,
+                &WordList::default(),
+                Language::English,
+            )
+        })
+        .collect();
+
+    // A mix of answers, other real words and gibberish of every length.
+    let mut guesses = curated.clone();
+    guesses.extend(["ABOUT", "ZYMURGY", "QQQQQ", "XKCDXKCD", "ABCD"].map(String::from));
+
+    for strictness in Strictness::value_variants() {
+        let allowed = AllowedGuesses::new(*strictness, Language::English, curated.clone());
+
+        let start = Instant::now();
+        black_box(allowed.allows("CRANE"));
+        println!("{strictness:?}: first lookup {:?}", start.elapsed());
+
+        let start = Instant::now();
+        for _ in 0..ROUNDS {
+            for guess in &guesses {
+                black_box(allowed.allows(black_box(guess)));
+            }
+        }
+        let lookups = ROUNDS * guesses.len() as u32;
+        let per_lookup = start.elapsed() / lookups;
+        println!("{strictness:?}: {lookups} lookups, {per_lookup:?} per lookup");
+
+        assert!(
+            per_lookup.as_secs_f64() < 0.001,
+            "{strictness:?} guess validation took {per_lookup:?}, over a millisecond"
+        );
+    }
+}
@@ -0,0 +1,231 @@
+//! Cleans up the word lists at build time, rather than every time one is loaded.
+//! Each GPT generated list is deduplicated, filtered and sorted, then embedded in
+//! `mints_lib` twice over, once for the `Gpt` and once for the `Webster` list. The
+//! frequency list is split up by length, keeping its order. The thesaurus is
+//! embedded for `definitions.rs` as a table to binary search.
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    env,
+    fmt::Write,
+    fs,
+    path::Path,
+};
+
+/// Keep in line with `MIN_LETTERS` and `MAX_LETTERS` in `lib.rs`.
+const MIN_LETTERS: usize = 3;
+const MAX_LETTERS: usize = 12;
+
+const DIFFICULTIES: [(char, &str); 3] = [('E', "Easy"), ('M', "Medium"), ('H', "Hard")];
+
+fn main() {
+    let mut code = String::from(
+        "/// The sanitised word list for the given letters, difficulty and `ListType`.\n\
+         /// Generated by `build.rs` from `word_lists/`.\n\
+         fn sanitised_list(letters: i32, diff: &Difficulty, list: ListType) -> &'static [&'static str] {\n\
+         \x20   match (letters, diff, list) {\n",
+    );
+
+    for letters in 4..=8 {
+        for (suffix, difficulty) in DIFFICULTIES {
+            let path = format!("word_lists/{letters}{suffix}.txt");
+            println!("cargo:rerun-if-changed={path}");
+
+            let raw = fs::read_to_string(&path).unwrap_or_else(|e| panic!("{path}: {e}"));
+            let (gpt, webster) = sanitise_gpt_list(&raw, letters);
+
+            for (list, words) in [("Gpt", gpt), ("Webster", webster)] {
+                writeln!(
+                    code,
+                    "        ({letters}, Difficulty::{difficulty}, ListType::{list}) => &{words:?},"
+                )
+                .unwrap();
+            }
+        }
+    }
+
+    code.push_str("        _ => &[],\n    }\n}\n");
+
+    let path = "word_lists/Frequency.txt";
+    println!("cargo:rerun-if-changed={path}");
+    let raw = fs::read_to_string(path).unwrap_or_else(|e| panic!("{path}: {e}"));
+
+    code.push_str(
+        "\n/// The `letters`-letter words of the frequency list, most common first.\n\
+         /// Generated by `build.rs` from `word_lists/Frequency.txt`.\n\
+         fn frequency_list(letters: i32) -> &'static [&'static str] {\n\
+         \x20   match letters {\n",
+    );
+    for (letters, words) in sanitise_frequency_list(&raw) {
+        writeln!(code, "        {letters} => &{words:?},").unwrap();
+    }
+    code.push_str("        _ => &[],\n    }\n}\n");
+
+    let out = Path::new(&env::var("OUT_DIR").unwrap()).join("word_lists.rs");
+    fs::write(out, code).expect("Failed to write the sanitised word lists");
+
+    let path = "word_lists/Thesaurus.txt";
+    println!("cargo:rerun-if-changed={path}");
+    let raw = fs::read_to_string(path).unwrap_or_else(|e| panic!("{path}: {e}"));
+
+    let mut code = String::from(
+        "/// Each word's WordNet synsets, as its part of speech and synonyms, sorted by word.\n\
+         /// Generated by `build.rs` from `word_lists/Thesaurus.txt`.\n\
+         static THESAURUS: &[(&str, &[Synset])] = &[\n",
+    );
+    for (word, synsets) in parse_thesaurus(&raw) {
+        write!(code, "    ({word:?}, &[").unwrap();
+        for (pos, synonyms) in synsets {
+            write!(code, "(PartOfSpeech::{pos}, &{synonyms:?}), ").unwrap();
+        }
+        code.push_str("]),\n");
+    }
+    code.push_str("];\n");
+
+    let out = Path::new(&env::var("OUT_DIR").unwrap()).join("thesaurus.rs");
+    fs::write(out, code).expect("Failed to write the thesaurus");
+}
+
+/// As the word lists are generated by GPT-3.5, this function takes the list generated
+/// online using OpenAI's website.
+///
+/// ## Prompt
+///
+/// > "ok im doing a word list for wordle, lets start with <4/5/6/7/8> letters, <easy/medium/hard>/
+/// > <common/uncommon/rare> words, 300 words in copyable code block and try your absolute
+/// > hardest to NOT repeat words"
+///
+/// Once copied into a txt file, this function captures repeats and makes sure each word
+/// is the specified letter amount. It also turns everything to uppercase for use in the game.
+///
+/// Two lists come out of the sanitisation, one with all words that meet the above criteria,
+/// and one with only the words which exist in the webster dictionary (70-80%). The dictionary
+/// lookup is done by the `webster` crate. Both are embedded, and which is played with is picked
+/// at runtime with `--word-list` (or `word_list` in the config). Webster doesn't have *all* words,
+/// but the GPT list won't guarantee actual words. Choice is yours. Neither is the default, the
+/// frequency list below is.
+///
+/// ## Word Counts
+///
+/// | Difficulty | GPT Count | Webster Count |
+/// |------------|-----------|---------------|
+/// | 4E         | 234       | 198           |
+/// | 4M         | 328       | 263           |
+/// | 4H         | 294       | 142           |
+/// | 5E         | 286       | 229           |
+/// | 5M         | 331       | 264           |
+/// | 5H         | 489       | 259           |
+/// | 6E         | 232       | 172           |
+/// | 6M         | 211       | 156           |
+/// | 6H         | 231       | 148           |
+/// | 7E         | 329       | 153           |
+/// | 7M         | 247       | 131           |
+/// | 7H         | 322       | 137           |
+/// | 8E         | 112       | 74            |
+/// | 8M         | 184       | 80            |
+/// | 8H         | 122       | 76            |
+///
+/// ## Overlap Counts
+/// | Letters | All Difficulties | Easy & Medium | Easy & Difficult | Medium & Difficult |
+/// |---------|------------------|---------------|------------------|--------------------|
+/// | 4       | 1                | 23            | 8                | 26                 |
+/// | 5       | 29               | 147           | 30               | 158                |
+/// | 6       | 10               | 51            | 16               | 36                 |
+/// | 7       | 12               | 64            | 26               | 44                 |
+/// | 8       | 0                | 19            | 0                | 1                  |
+///
+/// Because these are all generated with ChatGPT, it doesn't always isolate
+/// words to the difficulty categories.
+///
+fn sanitise_gpt_list(list: &str, letters: usize) -> (Vec<String>, Vec<String>) {
+    let gpt_list: BTreeSet<String> = list
+        .split_ascii_whitespace()
+        .filter(|word| word.len() == letters && word.chars().all(|c| c.is_ascii_alphabetic()))
+        .map(|word| word.to_ascii_uppercase())
+        .collect();
+
+    let webster_list = gpt_list
+        .iter()
+        .filter(|word| webster::dictionary(word).is_some())
+        .cloned()
+        .collect();
+
+    (gpt_list.into_iter().collect(), webster_list)
+}
+
+/// The frequency list is the English Wikipedia and US TV and film word lists that
+/// ship with [zxcvbn](https://github.com/dropbox/zxcvbn) (MIT), merged by taking
+/// each word's best rank of the two. It's ordered from the most common word to
+/// the rarest.
+///
+/// Names and other words that aren't in the webster dictionary are dropped, as
+/// well as any with something other than letters. Every length from
+/// `MIN_LETTERS` to `MAX_LETTERS` has at least 100 words left.
+///
+/// | Letters | Words | Letters | Words |
+/// |---------|-------|---------|-------|
+/// | 3       | 102   | 8       | 2010  |
+/// | 4       | 726   | 9       | 1894  |
+/// | 5       | 1690  | 10      | 1309  |
+/// | 6       | 1773  | 11      | 851   |
+/// | 7       | 2006  | 12      | 520   |
+fn sanitise_frequency_list(list: &str) -> BTreeMap<usize, Vec<String>> {
+    let mut seen = BTreeSet::new();
+    let mut lengths: BTreeMap<usize, Vec<String>> = BTreeMap::new();
+
+    for word in list.split_ascii_whitespace() {
+        let word = word.to_ascii_uppercase();
+        if (MIN_LETTERS..=MAX_LETTERS).contains(&word.len())
+            && word.chars().all(|c| c.is_ascii_alphabetic())
+            && webster::dictionary(&word).is_some()
+            && seen.insert(word.clone())
+        {
+            lengths.entry(word.len()).or_default().push(word);
+        }
+    }
+
+    lengths
+}
+
+/// The thesaurus is WordNet 3.0's synsets (© Princeton University, under the
+/// WordNet licence) as packaged by [thesaurus-rs](https://github.com/grantshandy/thesaurus-rs)
+/// (MIT), cut down to the words in the other lists. Synonyms that aren't WordNet
+/// words of their own (a few come truncated), names and anything under three
+/// letters are dropped.
+///
+/// Each line is a word followed by its synsets, tab separated. A synset is a
+/// WordNet part of speech (`n`, `v`, `a` or `r`) and its synonyms, e.g.
+/// `n:halt;stoppage`. Synsets without any synonyms (just `n:`) are only kept for
+/// a part of speech the word doesn't otherwise have.
+///
+/// | Words  | Synsets | With synonyms |
+/// |--------|---------|---------------|
+/// | 12376  | 31283   | 29437         |
+fn parse_thesaurus(list: &str) -> BTreeMap<&str, Vec<(&'static str, Vec<&str>)>> {
+    let mut thesaurus = BTreeMap::new();
+
+    for line in list.lines() {
+        let mut fields = line.split('\t');
+        let Some(word) = fields.next().filter(|word| !word.is_empty()) else {
+            continue;
+        };
+
+        let synsets = fields
+            .filter_map(|synset| {
+                let (pos, synonyms) = synset.split_once(':')?;
+                let pos = match pos {
+                    "n" => "Noun",
+                    "v" => "Verb",
+                    "a" => "Adjective",
+                    "r" => "Adverb",
+                    _ => panic!("Unknown part of speech `{pos}` for {word}"),
+                };
+                let synonyms = synonyms.split(';').filter(|s| !s.is_empty()).collect();
+                Some((pos, synonyms))
+            })
+            .collect();
+        thesaurus.insert(word, synsets);
+    }
+
+    thesaurus
+}
@@ -0,0 +1,153 @@
+use std::error::Error;
+
+use crate::{config, flush, out, outln, sleep, Console, Game, LoopOutcome, Outcome, Stats};
+
+/// A `!command` the player can type in place of a guess.
+#[derive(Clone, Debug)]
+pub struct Command {
+    pub name: &'static str,
+    pub aliases: &'static [&'static str],
+    pub description: &'static str,
+}
+
+impl Command {
+    fn matches(&self, name: &str) -> bool {
+        self.name == name || self.aliases.contains(&name)
+    }
+}
+
+/// The registry of commands available in a game. Every game gets the common
+/// commands below, and registers any of its own on top through `Game::commands`.
+#[derive(Clone, Debug)]
+pub struct Commands {
+    commands: Vec<Command>,
+}
+
+impl Default for Commands {
+    fn default() -> Self {
+        let mut commands = Commands {
+            commands: vec![
+                Command {
+                    name: "hint",
+                    aliases: &["h"],
+                    description: "Get a hint, each one gives away a little more",
+                },
+                Command {
+                    name: "restart",
+                    aliases: &["next", "reset", "r"],
+                    description: "Give up and start a new round",
+                },
+                Command {
+                    name: "quit",
+                    aliases: &["leave", "exit", "q"],
+                    description: "Quit the game",
+                },
+                Command {
+                    name: "stats",
+                    aliases: &["statistics"],
+                    description: "Show your stats for this game mode",
+                },
+                Command {
+                    name: "help",
+                    aliases: &["commands", "?"],
+                    description: "List the available commands",
+                },
+            ],
+        };
+
+        if !config().hints {
+            commands.commands.retain(|c| c.name != "hint");
+        }
+        commands
+    }
+}
+
+impl Commands {
+    /// Adds a game specific command.
+    pub fn register(
+        mut self,
+        name: &'static str,
+        aliases: &'static [&'static str],
+        description: &'static str,
+    ) -> Self {
+        self.commands.push(Command {
+            name,
+            aliases,
+            description,
+        });
+        self
+    }
+
+    /// Finds the command for the given input (e.g. `!h`), by name or alias.
+    pub fn resolve(&self, input: &str) -> Option<&Command> {
+        let name = input.trim().trim_start_matches('!').to_ascii_lowercase();
+        self.commands.iter().find(|c| c.matches(&name))
+    }
+
+    /// Lists every command with its aliases and description.
+    pub fn help(&self) -> String {
+        let names: Vec<String> = self
+            .commands
+            .iter()
+            .map(|c| {
+                let mut names = format!("!{}", c.name);
+                for alias in c.aliases {
+                    names.push_str(&format!(", !{alias}"));
+                }
+                names
+            })
+            .collect();
+        let width = names.iter().map(|n| n.len()).max().unwrap_or(0);
+
+        let mut help = String::from("Commands:");
+        for (names, command) in names.iter().zip(&self.commands) {
+            help.push_str(&format!("\n  {names:<width$}  {}", command.description));
+        }
+        help
+    }
+}
+
+/// Runs a `!command` typed during `game`. The common commands are handled here,
+/// anything else the game registered is passed on to `Game::run_command`.
+pub fn handle_command<G: Game>(
+    game: &mut G,
+    io: &mut Console,
+    input: &str,
+) -> Result<LoopOutcome, Box<dyn Error>> {
+    let commands = game.commands();
+    let Some(command) = commands.resolve(input) else {
+        outln!(io, "Unknown command! Try !help");
+        return Ok(LoopOutcome::Ongoing);
+    };
+
+    match command.name {
+        "hint" => game.hint(io),
+        "restart" => {
+            game.reveal(io);
+            countdown(io, G::RESTART_COUNTDOWN);
+            return Ok(LoopOutcome::Restart(game.result(Outcome::Abandoned)));
+        }
+        "quit" => return Ok(LoopOutcome::Over(game.result(Outcome::Abandoned))),
+        "stats" => {
+            let result = game.result(Outcome::Abandoned);
+            match Stats::load().mode(result.game, result.letters, &result.difficulty) {
+                Some(stats) => outln!(io, "{stats}"),
+                None => outln!(io, "No stats for this mode yet!"),
+            }
+        }
+        "help" => outln!(io, "{}", commands.help()),
+        name => return game.run_command(io, name),
+    }
+
+    Ok(LoopOutcome::Ongoing)
+}
+
+/// Counts down the given amount of seconds before a restart.
+fn countdown(io: &mut Console, secs: u64) {
+    out!(io, "Restarting in ");
+    for i in (1..=secs).rev() {
+        out!(io, "{i}.. ");
+        flush!(io);
+        sleep!(io, 1000);
+    }
+}
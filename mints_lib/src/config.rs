@@ -0,0 +1,279 @@
+use std::{collections::HashMap, fs, io, path::PathBuf, str::FromStr, sync::OnceLock};
+
+use colored::Color;
+use serde::Deserialize;
+use toml::Table;
+
+use crate::{
+    data_dir, Difficulty, JsonDefinitions, Language, Mints, Strictness, WordList, MAX_LETTERS,
+    MIN_LETTERS,
+};
+
+static CONFIG: OnceLock<Config> = OnceLock::new();
+
+/// The player's settings, read from TOML config files. The user's own config is
+/// read first, then a `mints.toml` in the working directory on top of it, and
+/// any flags given on the command line win over both. Anything left out keeps
+/// its built-in default.
+///
+/// ```toml
+/// guesses = 5
+/// letters = 6
+/// difficulty = "medium"
+/// hangman_letters = 8
+/// anagram_letters = 7
+/// word_list = "webster"
+/// language = "english"
+/// animation_speed = 2.0
+/// hints = true
+/// max_hints = 2
+/// definitions = "/home/mint/words/definitions.json"
+/// wordnet = "/usr/share/wordnet/dict"
+/// player = "mint"
+/// leaderboard = "/mnt/team/mints/leaderboard.json"
+///
+/// [strictness]
+/// wordle = "strict"
+/// anagrams = "standard"
+///
+/// [theme]
+/// correct = "bright green"
+/// present = "bright yellow"
+/// wrong = "magenta"
+/// title = "cyan"
+/// ```
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// Wordle's default guesses, when `--guesses` isn't given.
+    pub guesses: Option<i32>,
+
+    /// Wordle's default letters, when `--letters` isn't given.
+    pub letters: Option<i32>,
+
+    /// The default difficulty, when `--difficulty` isn't given.
+    pub difficulty: Option<Difficulty>,
+
+    /// The length of Hangman's words.
+    pub hangman_letters: Option<i32>,
+
+    /// The size of Anagrams' scrambles, and so its longest words.
+    pub anagram_letters: Option<usize>,
+
+    /// Where answers come from, when `--word-list` isn't given. Defaults to the
+    /// language's list.
+    pub word_list: Option<WordList>,
+
+    /// The language to play in, when `--language` isn't given.
+    pub language: Option<Language>,
+
+    /// How fast animations play, `2.0` being twice as fast. `0` turns them off.
+    pub animation_speed: f64,
+
+    /// Whether `!hint` is available.
+    pub hints: bool,
+
+    /// How many `!hint`s each round gets, see `HintTier` for what each gives.
+    pub max_hints: u32,
+
+    /// Each game's `Strictness`, when `--strictness` isn't given.
+    pub strictness: HashMap<Mints, Strictness>,
+
+    /// A JSON file of definitions to look words up in before WordNet and
+    /// Webster, see `JsonDefinitions`.
+    pub definitions: Option<PathBuf>,
+
+    /// A WordNet database directory, the one with `index.noun`, `data.noun` and
+    /// so on in it. Only WordNet's synonyms are shipped with mints, without it
+    /// hints have Webster's definitions (and the player's own) rather than
+    /// WordNet's.
+    pub wordnet: Option<PathBuf>,
+
+    /// The name scores go on the leaderboard under, the login name otherwise.
+    pub player: Option<String>,
+
+    /// Where the leaderboard is kept, e.g. a shared drive so a team can compare
+    /// their daily puzzles.
+    pub leaderboard: Option<PathBuf>,
+
+    pub theme: Theme,
+}
+
+/// The colors used for feedback. Any color `colored` knows by name will do,
+/// e.g. `"green"` or `"bright blue"`.
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default, deny_unknown_fields)]
+pub struct Theme {
+    /// Letters and words that are right.
+    pub correct: String,
+
+    /// Letters that are in the word, but somewhere else.
+    pub present: String,
+
+    /// Letters and words that are wrong.
+    pub wrong: String,
+
+    /// The big titles.
+    pub title: String,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            guesses: None,
+            letters: None,
+            difficulty: None,
+            hangman_letters: None,
+            anagram_letters: None,
+            word_list: None,
+            language: None,
+            animation_speed: 1.0,
+            hints: true,
+            max_hints: 4,
+            strictness: HashMap::new(),
+            definitions: None,
+            wordnet: None,
+            player: None,
+            leaderboard: None,
+            theme: Theme::default(),
+        }
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme {
+            correct: "green".to_string(),
+            present: "yellow".to_string(),
+            wrong: "red".to_string(),
+            title: "green".to_string(),
+        }
+    }
+}
+
+impl Config {
+    /// The user's config, `$XDG_CONFIG_HOME/mints/config.toml` on Linux.
+    pub fn user_path() -> Option<PathBuf> {
+        dirs::config_dir().map(|dir| dir.join("mints").join("config.toml"))
+    }
+
+    /// The project-local config, `mints.toml` in the working directory.
+    pub fn local_path() -> PathBuf {
+        PathBuf::from("mints.toml")
+    }
+
+    /// Reads and layers the config files. Missing files are skipped, but ones
+    /// that can't be parsed, or hold invalid settings, are an error.
+    pub fn load() -> io::Result<Self> {
+        let mut table = Table::new();
+        for path in Self::user_path().into_iter().chain([Self::local_path()]) {
+            let text = match fs::read_to_string(&path) {
+                Ok(text) => text,
+                Err(e) if e.kind() == io::ErrorKind::NotFound => continue,
+                Err(e) => return Err(e),
+            };
+
+            let layer = text
+                .parse::<Table>()
+                .map_err(|e| invalid(format!("{}: {e}", path.display())))?;
+            merge(&mut table, layer);
+        }
+
+        let config: Config = table.try_into().map_err(|e| invalid(e.to_string()))?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// The player's own definitions, from `definitions` or else
+    /// `$XDG_CONFIG_HOME/mints/definitions.json` if there is one.
+    pub fn definitions_path(&self) -> Option<PathBuf> {
+        self.definitions.clone().or_else(|| {
+            dirs::config_dir()
+                .map(|dir| dir.join("mints").join("definitions.json"))
+                .filter(|path| path.is_file())
+        })
+    }
+
+    /// The WordNet database, from `wordnet` or else `$XDG_DATA_HOME/mints/wordnet`
+    /// if there is one.
+    pub fn wordnet_dir(&self) -> Option<PathBuf> {
+        self.wordnet.clone().or_else(|| {
+            data_dir()
+                .map(|dir| dir.join("wordnet"))
+                .filter(|dir| dir.is_dir())
+        })
+    }
+
+    /// Makes this the config returned by `config()`. Only the first call has any
+    /// effect, so it should be done once at startup.
+    pub fn install(self) {
+        let _ = CONFIG.set(self);
+    }
+
+    fn validate(&self) -> io::Result<()> {
+        let in_range = |name: &str, value: Option<i32>, min: i32, max: i32| match value {
+            Some(n) if !(min..=max).contains(&n) => {
+                Err(invalid(format!("{name} must be between {min} and {max}")))
+            }
+            _ => Ok(()),
+        };
+        in_range("guesses", self.guesses, 3, 9)?;
+        in_range("letters", self.letters, MIN_LETTERS, MAX_LETTERS)?;
+        in_range(
+            "hangman_letters",
+            self.hangman_letters,
+            MIN_LETTERS,
+            MAX_LETTERS,
+        )?;
+        in_range(
+            "anagram_letters",
+            self.anagram_letters.map(|n| n as i32),
+            6,
+            8,
+        )?;
+
+        if self.animation_speed.is_nan() || self.animation_speed < 0.0 {
+            return Err(invalid("animation_speed can't be negative".to_string()));
+        }
+
+        if let Some(path) = self.definitions_path() {
+            JsonDefinitions::load(&path)?;
+        }
+        if let Some(dir) = &self.wordnet {
+            if !dir.join("index.noun").is_file() {
+                return Err(invalid(format!(
+                    "{} isn't a WordNet database",
+                    dir.display()
+                )));
+            }
+        }
+
+        let theme = &self.theme;
+        for color in [&theme.correct, &theme.present, &theme.wrong, &theme.title] {
+            Color::from_str(color).map_err(|_| invalid(format!("{color} isn't a color")))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The config in use, the defaults until one is installed.
+pub fn config() -> &'static Config {
+    CONFIG.get_or_init(Config::default)
+}
+
+/// Lays `layer` over `base`, merging tables (like `[theme]`) key by key.
+fn merge(base: &mut Table, layer: Table) {
+    for (key, value) in layer {
+        match (base.get_mut(&key), value) {
+            (Some(toml::Value::Table(base)), toml::Value::Table(layer)) => merge(base, layer),
+            (_, value) => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
+fn invalid(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
@@ -0,0 +1,87 @@
+use std::io::{self, BufRead, BufReader, Write};
+
+use crate::config;
+
+/// The input source and output sink a `Game` talks through. The `mint_cli` hands
+/// every game the process' stdin/stdout, but anything readable/writable can be
+/// plugged in instead (scripts, sockets, tests, other frontends...).
+///
+/// A headless console skips screen clears and animation delays, and games won't
+/// prompt through it to play again, so whole rounds can be driven by piped input.
+pub struct Console {
+    input: Box<dyn BufRead>,
+    output: Box<dyn Write>,
+    headless: bool,
+}
+
+impl Console {
+    pub fn new(input: impl BufRead + 'static, output: impl Write + 'static) -> Self {
+        Console {
+            input: Box::new(input),
+            output: Box::new(output),
+            headless: false,
+        }
+    }
+
+    /// A console reading from stdin and writing to stdout.
+    pub fn stdio() -> Self {
+        Self::new(BufReader::new(io::stdin()), io::stdout())
+    }
+
+    /// A headless console reading from stdin. Everything the games print goes
+    /// to stderr, leaving stdout free for machine-readable results.
+    pub fn headless() -> Self {
+        Console {
+            headless: true,
+            ..Self::new(BufReader::new(io::stdin()), io::stderr())
+        }
+    }
+
+    pub fn set_headless(&mut self, headless: bool) {
+        self.headless = headless;
+    }
+
+    pub fn is_headless(&self) -> bool {
+        self.headless
+    }
+
+    /// Pauses for the given amount of milliseconds, scaled by the configured
+    /// animation speed, unless headless.
+    pub fn sleep(&self, millis: u64) {
+        let speed = config().animation_speed;
+        if !self.headless && speed > 0.0 {
+            std::thread::sleep(std::time::Duration::from_secs_f64(
+                millis as f64 / 1000.0 / speed,
+            ));
+        }
+    }
+
+    /// Reads the next line of input without its line ending. Returns `None` once
+    /// the input has been exhausted.
+    pub fn read_line(&mut self) -> io::Result<Option<String>> {
+        let mut line = String::new();
+        if self.input.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+
+        let len = line.trim_end_matches(['\r', '\n']).len();
+        line.truncate(len);
+        Ok(Some(line))
+    }
+}
+
+impl Default for Console {
+    fn default() -> Self {
+        Self::stdio()
+    }
+}
+
+impl Write for Console {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.output.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.output.flush()
+    }
+}
@@ -0,0 +1,559 @@
+use std::{
+    collections::HashMap,
+    fmt,
+    fs::{self, File},
+    io::{self, BufRead, BufReader, Seek, SeekFrom},
+    path::{Path, PathBuf},
+    sync::OnceLock,
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::config;
+
+static DEFINITIONS: OnceLock<Definitions> = OnceLock::new();
+
+/// What a word means, as found by a `DefinitionSource`.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Entry {
+    pub word: String,
+    pub senses: Vec<Sense>,
+    pub synonyms: Vec<String>,
+}
+
+/// One of the meanings of a word.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Sense {
+    pub part_of_speech: Option<PartOfSpeech>,
+    pub definition: String,
+    pub examples: Vec<String>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PartOfSpeech {
+    Noun,
+    Verb,
+    Adjective,
+    Adverb,
+}
+
+/// Somewhere words can be looked up.
+pub trait DefinitionSource: Send + Sync {
+    /// The entry for the (lowercase) word, if the source has one.
+    fn lookup(&self, word: &str) -> Option<Entry>;
+}
+
+/// Every `DefinitionSource` in use, tried in turn until one knows the word: the
+/// player's own JSON definitions, then a WordNet database, then the embedded
+/// ones. The first two are only used when they're found, see
+/// `Config::definitions_path` and `Config::wordnet_dir`.
+pub struct Definitions {
+    sources: Vec<Box<dyn DefinitionSource>>,
+}
+
+impl Definitions {
+    /// The shared definitions, set up on first use from the config.
+    pub fn get() -> &'static Self {
+        DEFINITIONS.get_or_init(Self::build)
+    }
+
+    fn build() -> Self {
+        let mut sources: Vec<Box<dyn DefinitionSource>> = Vec::new();
+
+        // Already checked when the config was loaded.
+        if let Some(json) = config()
+            .definitions_path()
+            .and_then(|path| JsonDefinitions::load(&path).ok())
+        {
+            sources.push(Box::new(json));
+        }
+        if let Some(dir) = config().wordnet_dir() {
+            sources.push(Box::new(WordNet::new(dir)));
+        }
+        sources.push(Box::new(Embedded));
+
+        Definitions { sources }
+    }
+
+    /// The first entry found for the word, in any case. Inflections without an
+    /// entry of their own fall back to their stem, so `baked` finds `bake`.
+    pub fn lookup(&self, word: &str) -> Option<Entry> {
+        stems(&word.to_lowercase())
+            .iter()
+            .find_map(|stem| self.sources.iter().find_map(|source| source.lookup(stem)))
+    }
+}
+
+/// A WordNet synset: a part of speech and the words that mean the same in it.
+type Synset = (PartOfSpeech, &'static [&'static str]);
+
+include!(concat!(env!("OUT_DIR"), "/thesaurus.rs"));
+
+/// The 1913 Webster's embedded by the `webster` crate, along with the WordNet
+/// synsets `build.rs` embeds for the words in the lists. Webster's definition
+/// comes first (with its part of speech when WordNet only has the one), then a
+/// sense for each synset with synonyms.
+pub struct Embedded;
+
+impl Embedded {
+    /// The word's synsets, see `parse_thesaurus` in `build.rs`.
+    fn synsets(word: &str) -> &'static [Synset] {
+        THESAURUS
+            .binary_search_by_key(&word, |(word, _)| word)
+            .map_or(&[], |i| THESAURUS[i].1)
+    }
+}
+
+impl DefinitionSource for Embedded {
+    fn lookup(&self, word: &str) -> Option<Entry> {
+        let synsets = Self::synsets(word);
+        let mut parts = synsets.iter().map(|(pos, _)| *pos);
+        let part_of_speech = parts.next().filter(|first| parts.all(|pos| pos == *first));
+
+        let mut entry = Entry {
+            word: word.to_string(),
+            ..Default::default()
+        };
+        if let Some(definition) = webster::dictionary(word) {
+            entry.senses.push(Sense {
+                part_of_speech,
+                definition: definition.to_string(),
+                ..Default::default()
+            });
+        }
+        for (pos, synonyms) in synsets.iter().filter(|(_, synonyms)| !synonyms.is_empty()) {
+            entry.senses.push(Sense {
+                part_of_speech: Some(*pos),
+                definition: synonyms.join(", "),
+                ..Default::default()
+            });
+            for synonym in *synonyms {
+                if !entry.synonyms.iter().any(|s| s == synonym) {
+                    entry.synonyms.push(synonym.to_string());
+                }
+            }
+        }
+
+        (!entry.senses.is_empty()).then_some(entry)
+    }
+}
+
+/// A dictionary of the player's own, a JSON object of words to entries:
+///
+/// ```json
+/// {
+///     "crane": {
+///         "senses": [
+///             {
+///                 "part_of_speech": "noun",
+///                 "definition": "A large wading bird with a long neck",
+///                 "examples": ["A crane stood in the shallows."]
+///             }
+///         ],
+///         "synonyms": ["heron"]
+///     }
+/// }
+/// ```
+pub struct JsonDefinitions {
+    entries: HashMap<String, Entry>,
+}
+
+impl JsonDefinitions {
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let text = fs::read_to_string(path)
+            .map_err(|e| io::Error::new(e.kind(), format!("{}: {e}", path.display())))?;
+        let entries: HashMap<String, Entry> = serde_json::from_str(&text).map_err(|e| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("{}: {e}", path.display()),
+            )
+        })?;
+
+        let entries = entries
+            .into_iter()
+            .map(|(word, entry)| {
+                let word = word.to_lowercase();
+                (word.clone(), Entry { word, ..entry })
+            })
+            .collect();
+        Ok(JsonDefinitions { entries })
+    }
+}
+
+impl DefinitionSource for JsonDefinitions {
+    fn lookup(&self, word: &str) -> Option<Entry> {
+        self.entries.get(word).cloned()
+    }
+}
+
+/// A WordNet database, the `index.*` and `data.*` files of its `dict` directory.
+/// The indexes are read the first time a word is looked up, after which each
+/// sense is read straight from its offset in the data files.
+pub struct WordNet {
+    dir: PathBuf,
+    index: OnceLock<HashMap<String, Vec<(PartOfSpeech, u64)>>>,
+}
+
+impl WordNet {
+    const FILES: [(PartOfSpeech, &'static str); 4] = [
+        (PartOfSpeech::Noun, "noun"),
+        (PartOfSpeech::Verb, "verb"),
+        (PartOfSpeech::Adjective, "adj"),
+        (PartOfSpeech::Adverb, "adv"),
+    ];
+
+    pub fn new(dir: PathBuf) -> Self {
+        WordNet {
+            dir,
+            index: OnceLock::new(),
+        }
+    }
+
+    /// Maps each lemma to the offsets of its synsets, see `parse_index_line`.
+    fn build_index(&self) -> HashMap<String, Vec<(PartOfSpeech, u64)>> {
+        let mut index: HashMap<String, Vec<(PartOfSpeech, u64)>> = HashMap::new();
+
+        for (pos, name) in Self::FILES {
+            let Ok(text) = fs::read_to_string(self.dir.join(format!("index.{name}"))) else {
+                continue;
+            };
+
+            for (lemma, offsets) in text.lines().filter_map(parse_index_line) {
+                index
+                    .entry(lemma)
+                    .or_default()
+                    .extend(offsets.into_iter().map(|offset| (pos, offset)));
+            }
+        }
+
+        index
+    }
+
+    /// Reads the synset at `offset`, see `parse_synset`.
+    fn read_synset(&self, pos: PartOfSpeech, offset: u64) -> Option<(Sense, Vec<String>)> {
+        let name = Self::FILES.iter().find(|(p, _)| *p == pos)?.1;
+        let mut file = File::open(self.dir.join(format!("data.{name}"))).ok()?;
+        file.seek(SeekFrom::Start(offset)).ok()?;
+        let mut line = String::new();
+        BufReader::new(file).read_line(&mut line).ok()?;
+
+        parse_synset(pos, &line)
+    }
+}
+
+/// A line of a WordNet index, `lemma pos synset_cnt p_cnt [ptr_symbol...]
+/// sense_cnt tagsense_cnt synset_offset...`, as the lemma (with spaces) and the
+/// offsets of its synsets. Lines starting with a space are the licence.
+fn parse_index_line(line: &str) -> Option<(String, Vec<u64>)> {
+    if line.starts_with(' ') {
+        return None;
+    }
+
+    let fields: Vec<&str> = line.split_ascii_whitespace().collect();
+    let synsets: usize = fields.get(2)?.parse().ok()?;
+    let offsets = fields
+        .iter()
+        .rev()
+        .take(synsets)
+        .rev()
+        .filter_map(|offset| offset.parse().ok())
+        .collect();
+
+    Some((fields.first()?.replace('_', " "), offsets))
+}
+
+/// A line of a WordNet data file, `synset_offset lex_filenum ss_type w_cnt
+/// [word lex_id...] ... | gloss`, as its sense and the words in the synset. The
+/// gloss is the definition followed by any quoted examples, split by semicolons.
+fn parse_synset(pos: PartOfSpeech, line: &str) -> Option<(Sense, Vec<String>)> {
+    let (fields, gloss) = line.split_once(" | ")?;
+    let fields: Vec<&str> = fields.split_ascii_whitespace().collect();
+    let words = usize::from_str_radix(fields.get(3)?, 16).ok()?;
+    let synonyms = fields
+        .iter()
+        .skip(4)
+        .step_by(2)
+        .take(words)
+        // Adjectives can be marked with their position, e.g. `galore(ip)`.
+        .map(|word| word.split('(').next().unwrap_or(word).replace('_', " "))
+        .collect();
+
+    let (mut definition, mut examples) = (Vec::new(), Vec::new());
+    for part in gloss.trim().split("; ") {
+        match part.trim().strip_prefix('"') {
+            Some(example) => examples.push(example.trim_end_matches('"').to_string()),
+            None => definition.push(part.trim()),
+        }
+    }
+
+    let sense = Sense {
+        part_of_speech: Some(pos),
+        definition: definition.join("; "),
+        examples,
+    };
+    Some((sense, synonyms))
+}
+
+impl DefinitionSource for WordNet {
+    fn lookup(&self, word: &str) -> Option<Entry> {
+        let synsets = self.index.get_or_init(|| self.build_index()).get(word)?;
+
+        let mut entry = Entry {
+            word: word.to_string(),
+            ..Default::default()
+        };
+        for (sense, synonyms) in synsets
+            .iter()
+            .filter_map(|(pos, offset)| self.read_synset(*pos, *offset))
+        {
+            entry.senses.push(sense);
+            for synonym in synonyms {
+                if synonym.to_lowercase() != word && !entry.synonyms.contains(&synonym) {
+                    entry.synonyms.push(synonym);
+                }
+            }
+        }
+
+        (!entry.senses.is_empty()).then_some(entry)
+    }
+}
+
+impl fmt::Display for PartOfSpeech {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PartOfSpeech::Noun => write!(f, "noun"),
+            PartOfSpeech::Verb => write!(f, "verb"),
+            PartOfSpeech::Adjective => write!(f, "adjective"),
+            PartOfSpeech::Adverb => write!(f, "adverb"),
+        }
+    }
+}
+
+impl fmt::Display for Sense {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.part_of_speech {
+            Some(pos) => write!(f, "({pos}) {}", self.definition),
+            None => write!(f, "{}", self.definition),
+        }
+    }
+}
+
+/// Hides the word, and any of its inflections (plurals, tenses and the like),
+/// wherever they show up in the text, e.g. in a definition used as a hint.
+pub fn mask(text: &str, word: &str) -> String {
+    let forms = inflections(&word.to_lowercase());
+    let mut masked = String::new();
+    let mut token = String::new();
+
+    for c in text.chars().chain([' ']) {
+        if c.is_alphabetic() {
+            token.push(c);
+            continue;
+        }
+
+        match forms.contains(&token.to_lowercase()) {
+            true => masked.push_str(&format!("[{}]", "_".repeat(token.chars().count()))),
+            false => masked.push_str(&token),
+        }
+        token.clear();
+        masked.push(c);
+    }
+
+    masked.pop();
+    masked
+}
+
+/// The word itself followed by the stems it might have been inflected from.
+fn stems(word: &str) -> Vec<String> {
+    let mut stems = vec![word.to_string()];
+    for suffix in ["s", "es", "ed", "d", "ing", "er", "est", "ly"] {
+        if let Some(stem) = word.strip_suffix(suffix).filter(|stem| stem.len() >= 3) {
+            stems.push(stem.to_string());
+            stems.push(format!("{stem}e"));
+        }
+    }
+    for suffix in ["ies", "ied", "ier", "iest", "ily"] {
+        if let Some(stem) = word.strip_suffix(suffix).filter(|stem| stem.len() >= 2) {
+            stems.push(format!("{stem}y"));
+        }
+    }
+    stems
+}
+
+/// The word along with the forms English builds from it. The word might be an
+/// inflection itself (`cranes`), so its likely stems are inflected as well.
+fn inflections(word: &str) -> Vec<String> {
+    let mut forms = Vec::new();
+    for stem in stems(word) {
+        let suffixes = ["", "s", "es", "ed", "ing", "er", "ers", "est", "ly"];
+        forms.extend(suffixes.iter().map(|suffix| format!("{stem}{suffix}")));
+
+        // bake -> baking, baked
+        if let Some(root) = stem.strip_suffix('e') {
+            forms.extend(["ing", "ed", "er", "ers", "est"].map(|suffix| format!("{root}{suffix}")));
+        }
+        // carry -> carries, carried
+        if let Some(root) = stem.strip_suffix('y') {
+            forms.extend(["ies", "ied", "ier", "iest", "ily"].map(|s| format!("{root}{s}")));
+        }
+        // stop -> stopped, stopping
+        if let Some(last) = stem.chars().last().filter(|c| !"aeiouwxy".contains(*c)) {
+            forms.extend(["ed", "ing", "er", "est"].map(|s| format!("{stem}{last}{s}")));
+        }
+    }
+
+    forms.sort_unstable();
+    forms.dedup();
+    forms
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stems_undo_suffixes() {
+        assert_eq!(stems("crane"), ["crane"]);
+        assert!(stems("cranes").contains(&"crane".to_string()));
+        assert!(stems("baked").contains(&"bake".to_string()));
+        assert!(stems("baking").contains(&"bake".to_string()));
+        assert!(stems("carried").contains(&"carry".to_string()));
+        assert!(stems("happily").contains(&"happy".to_string()));
+    }
+
+    #[test]
+    fn stems_keep_three_letters() {
+        // `bed` would be `b` without its `ed`.
+        assert_eq!(stems("bed"), ["bed"]);
+        assert!(!stems("sing").contains(&"s".to_string()));
+    }
+
+    #[test]
+    fn inflections_of_a_stem() {
+        let forms = inflections("bake");
+        for form in ["bake", "bakes", "baked", "baking", "baker"] {
+            assert!(forms.contains(&form.to_string()), "{form}");
+        }
+
+        let forms = inflections("carry");
+        for form in ["carry", "carries", "carried", "carrier"] {
+            assert!(forms.contains(&form.to_string()), "{form}");
+        }
+
+        let forms = inflections("stop");
+        for form in ["stop", "stops", "stopped", "stopping"] {
+            assert!(forms.contains(&form.to_string()), "{form}");
+        }
+    }
+
+    #[test]
+    fn inflections_of_an_inflection() {
+        let forms = inflections("cranes");
+        assert!(forms.contains(&"crane".to_string()));
+        assert!(forms.contains(&"craning".to_string()));
+    }
+
+    #[test]
+    fn mask_hides_the_word_and_its_forms() {
+        assert_eq!(
+            mask("To bake is what bakers do when baking.", "bake"),
+            "To [____] is what [______] do when [______]."
+        );
+        assert_eq!(mask("Cranes, a crane!", "CRANE"), "[______], a [_____]!");
+    }
+
+    #[test]
+    fn mask_leaves_other_words() {
+        assert_eq!(mask("A large wading bird", "crane"), "A large wading bird");
+        // Only whole words are masked.
+        assert_eq!(mask("Cranberry sauce", "cran"), "Cranberry sauce");
+        assert_eq!(mask("", "crane"), "");
+    }
+
+    #[test]
+    fn index_line_lemma_and_offsets() {
+        let line = "wading_bird n 1 2 @ ~ 1 0 02000954  ";
+        assert_eq!(
+            parse_index_line(line),
+            Some(("wading bird".to_string(), vec![2000954]))
+        );
+
+        let line = "crane n 2 4 @ ~ #m %p 2 1 02012849 03126707  ";
+        assert_eq!(
+            parse_index_line(line),
+            Some(("crane".to_string(), vec![2012849, 3126707]))
+        );
+    }
+
+    #[test]
+    fn index_licence_is_skipped() {
+        assert_eq!(parse_index_line("  1 This software and database is"), None);
+        assert_eq!(parse_index_line(""), None);
+    }
+
+    #[test]
+    fn synset_sense_and_synonyms() {
+        let line = "02012849 05 n 02 crane 0 Grus_americana 0 001 @ 02011553 n 0000 \
+                    | large long-necked wading bird; \"a crane stood in the shallows\"\n";
+        let (sense, synonyms) = parse_synset(PartOfSpeech::Noun, line).unwrap();
+
+        assert_eq!(sense.part_of_speech, Some(PartOfSpeech::Noun));
+        assert_eq!(sense.definition, "large long-necked wading bird");
+        assert_eq!(sense.examples, ["a crane stood in the shallows"]);
+        assert_eq!(synonyms, ["crane", "Grus americana"]);
+    }
+
+    #[test]
+    fn synset_adjective_markers_are_dropped() {
+        let line = "00016135 00 s 02 galore(ip) 0 in_abundance(ip) 0 000 \
+                    | in great numbers; \"food and wine galore\"";
+        let (sense, synonyms) = parse_synset(PartOfSpeech::Adjective, line).unwrap();
+
+        assert_eq!(sense.to_string(), "(adjective) in great numbers");
+        assert_eq!(synonyms, ["galore", "in abundance"]);
+    }
+
+    #[test]
+    fn synset_without_gloss() {
+        assert!(parse_synset(PartOfSpeech::Noun, "02012849 05 n 01 crane 0 000").is_none());
+    }
+
+    #[test]
+    fn embedded_senses_and_synonyms() {
+        let entry = Embedded.lookup("stop").unwrap();
+        assert!(entry.senses.len() > 2);
+        assert!(entry.senses[1..]
+            .iter()
+            .all(|sense| sense.part_of_speech.is_some()));
+        for synonym in ["halt", "cease", "block"] {
+            assert!(entry.synonyms.contains(&synonym.to_string()), "{synonym}");
+        }
+        // Each synonym only once, however many synsets it's in.
+        let halts = entry.synonyms.iter().filter(|s| *s == "halt").count();
+        assert_eq!(halts, 1);
+    }
+
+    #[test]
+    fn embedded_webster_comes_first() {
+        // WordNet only has `bake` as a verb, so Webster's definition is one too.
+        let entry = Embedded.lookup("bake").unwrap();
+        assert_eq!(entry.senses[0].part_of_speech, Some(PartOfSpeech::Verb));
+        assert_eq!(
+            entry.senses[0].definition,
+            webster::dictionary("bake").unwrap()
+        );
+
+        // `crane` is a noun and a verb, and Webster doesn't say which it means.
+        let entry = Embedded.lookup("crane").unwrap();
+        assert_eq!(entry.senses[0].part_of_speech, None);
+        assert_eq!(entry.senses[1].to_string(), "(verb) stretch out");
+    }
+
+    #[test]
+    fn embedded_unknown_word() {
+        assert!(Embedded.lookup("xkcdq").is_none());
+    }
+}
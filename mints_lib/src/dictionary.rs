@@ -0,0 +1,71 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::OnceLock,
+};
+
+use clap::ValueEnum;
+
+use crate::{
+    frequency_list, sanitised_list, Difficulty, ListType, MAX_LETTERS, MIN_LETTERS, WORDS_MASTER,
+};
+
+static DICTIONARY: OnceLock<Dictionary> = OnceLock::new();
+
+/// Every word a guess is checked against, uppercase and indexed by length. It's
+/// built from `WORDS_MASTER` and the answer lists once, the first time a word is
+/// looked up, and then shared by every game for the rest of the session.
+#[derive(Debug, Default)]
+pub struct Dictionary {
+    words: HashMap<usize, HashSet<String>>,
+}
+
+impl Dictionary {
+    /// The shared dictionary, built on first use.
+    pub fn get() -> &'static Self {
+        DICTIONARY.get_or_init(Self::build)
+    }
+
+    /// Indexes the master list along with all of the answer lists, so that any
+    /// answer can also be guessed.
+    fn build() -> Self {
+        let mut dictionary = Dictionary::default();
+
+        for word in WORDS_MASTER.split_ascii_whitespace() {
+            if word.chars().all(|c| c.is_ascii_alphabetic()) {
+                dictionary.insert(word.to_ascii_uppercase());
+            }
+        }
+
+        for letters in 4..=8 {
+            for difficulty in Difficulty::value_variants() {
+                for word in sanitised_list(letters, difficulty, ListType::Gpt) {
+                    dictionary.insert(word.to_string());
+                }
+            }
+        }
+
+        for letters in MIN_LETTERS..=MAX_LETTERS {
+            for word in frequency_list(letters) {
+                dictionary.insert(word.to_string());
+            }
+        }
+
+        dictionary
+    }
+
+    fn insert(&mut self, word: String) {
+        self.words.entry(word.len()).or_default().insert(word);
+    }
+
+    /// Whether the word (in any case) is in the dictionary.
+    pub fn contains(&self, word: &str) -> bool {
+        self.words
+            .get(&word.len())
+            .is_some_and(|words| words.contains(&word.to_ascii_uppercase()))
+    }
+
+    /// How many words of the given length there are.
+    pub fn count(&self, letters: usize) -> usize {
+        self.words.get(&letters).map_or(0, HashSet::len)
+    }
+}
@@ -0,0 +1,130 @@
+use serde::{Deserialize, Serialize};
+
+use crate::MAX_LETTERS;
+
+/// How a single letter of a Wordle guess compares to the answer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LetterResult {
+    /// The right letter in the right spot.
+    Correct,
+    /// In the answer, but somewhere else.
+    Present,
+    /// Not in the answer, or not as many times as guessed.
+    Absent,
+}
+
+impl LetterResult {
+    /// `G` (green), `Y` (yellow) or `-`, as shown in a round's history.
+    pub fn mark(&self) -> char {
+        match self {
+            LetterResult::Correct => 'G',
+            LetterResult::Present => 'Y',
+            LetterResult::Absent => '-',
+        }
+    }
+}
+
+/// Compares each letter of `guess` to `answer`. Letters in the right spot are
+/// marked first, then the rest are marked present from left to right for as
+/// long as the answer has unmarked copies of them left, so a letter guessed
+/// more times than it's in the answer only lights up as often as it's there.
+/// Both are expected to be normalised (see `Language::normalise`), so letters
+/// compare char by char. A guess of a different length is only scored as far
+/// as both go.
+pub fn score_guess(answer: &str, guess: &str) -> Vec<LetterResult> {
+    let answer: Vec<char> = answer.chars().collect();
+    let guess: Vec<char> = guess.chars().collect();
+
+    let mut results = vec![LetterResult::Absent; guess.len().min(answer.len())];
+    score_letters(&answer, &guess, &mut results);
+    results
+}
+
+/// `score_guess` for words already split into letters, writing into `results`
+/// (as long as the shorter word) so the solver can score without allocating.
+pub(crate) fn score_letters(answer: &[char], guess: &[char], results: &mut [LetterResult]) {
+    for ((g, a), result) in guess.iter().zip(answer).zip(results.iter_mut()) {
+        *result = match g == a {
+            true => LetterResult::Correct,
+            false => LetterResult::Absent,
+        };
+    }
+
+    // The answer's letters that weren't matched in place. Words are short, so
+    // a scan beats hashing.
+    let mut remaining = [None; MAX_LETTERS as usize];
+    for ((a, result), slot) in answer.iter().zip(results.iter()).zip(&mut remaining) {
+        if *result != LetterResult::Correct {
+            *slot = Some(*a);
+        }
+    }
+
+    for (g, result) in guess.iter().zip(results.iter_mut()) {
+        if *result == LetterResult::Absent {
+            if let Some(slot) = remaining.iter_mut().find(|slot| **slot == Some(*g)) {
+                *slot = None;
+                *result = LetterResult::Present;
+            }
+        }
+    }
+}
+
+/// The marks of each result, e.g. `GY--G`.
+pub fn marks(results: &[LetterResult]) -> String {
+    results.iter().map(LetterResult::mark).collect()
+}
+
+/// Checks a guess against what the `previous` guesses and their results
+/// revealed, for Wordle's hard mode. Each green letter has to stay in its spot
+/// and each yellow letter has to be used again (as many times as it was found),
+/// returning the first rule the guess breaks.
+pub fn hard_mode_violation(
+    previous: impl IntoIterator<Item = (impl AsRef<str>, impl AsRef<[LetterResult]>)>,
+    guess: &str,
+) -> Option<String> {
+    let letters: Vec<char> = guess.chars().collect();
+    for (previous, results) in previous {
+        let feedback: Vec<(char, LetterResult)> = previous
+            .as_ref()
+            .chars()
+            .zip(results.as_ref().iter().copied())
+            .collect();
+
+        for (i, (letter, result)) in feedback.iter().enumerate() {
+            if *result == LetterResult::Correct && letters.get(i) != Some(letter) {
+                return Some(format!("The {} letter has to be {letter}", ordinal(i + 1)));
+            }
+        }
+
+        let found_in = |(_, result): &&(char, LetterResult)| *result != LetterResult::Absent;
+        for (letter, _) in feedback.iter().filter(found_in) {
+            let found = feedback
+                .iter()
+                .filter(found_in)
+                .filter(|(l, _)| l == letter)
+                .count();
+            let used = letters.iter().filter(|l| *l == letter).count();
+            if used < found {
+                return Some(match found {
+                    1 => format!("{guess} has to use {letter}"),
+                    n => format!("{guess} has to use {letter} {n} times"),
+                });
+            }
+        }
+    }
+
+    None
+}
+
+/// `1st`, `2nd`, `3rd`, `4th` and so on.
+fn ordinal(n: usize) -> String {
+    let suffix = match (n % 10, n % 100) {
+        (_, 11..=13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    };
+    format!("{n}{suffix}")
+}
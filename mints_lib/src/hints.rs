@@ -0,0 +1,117 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{config, define, mask, outln, Console};
+
+/// How many tiers there are before they start repeating.
+const HINT_TIERS: u32 = 4;
+
+/// What each `!hint` of a round gives away, a little more each time.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HintTier {
+    /// The first sense of the answer, masked, see `hint`.
+    Definition,
+    /// The first letter of the answer.
+    FirstLetter,
+    /// A letter of the answer the player hasn't found yet, and where it goes.
+    Letter,
+    /// Other words that mean the same, or another sense if there aren't any.
+    Synonyms,
+}
+
+impl HintTier {
+    /// The tier of the `n`th hint (from 0). Once the synonyms are given, every
+    /// hint after reveals another letter.
+    pub fn nth(n: u32) -> Self {
+        match n {
+            0 => HintTier::Definition,
+            1 => HintTier::FirstLetter,
+            2 => HintTier::Letter,
+            3 => HintTier::Synonyms,
+            _ => HintTier::Letter,
+        }
+    }
+}
+
+/// The hints given so far in a round, up to `max_hints` from the config.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Hints {
+    pub used: u32,
+
+    /// How far through the tiers the hints for the current word are.
+    tier: u32,
+
+    /// The positions of the current word given away by hints.
+    revealed: Vec<usize>,
+}
+
+impl Hints {
+    /// Gives the next hint for the answer, unless the round is out of them.
+    /// `known` marks the positions the player has found for themselves, which
+    /// aren't worth revealing. Tiers with nothing to give for the answer (e.g. no
+    /// synonyms) are skipped, and only a hint that's given counts.
+    pub fn give(&mut self, io: &mut Console, answer: &str, known: &[bool]) {
+        let max = config().max_hints;
+        if self.used >= max {
+            outln!(io, "No hints left this round! (Max: {max})");
+            return;
+        }
+
+        // Every tier after the synonyms is a letter, so there's no need to look
+        // further than one past them.
+        let hint = (0..=HINT_TIERS).find_map(|_| {
+            let tier = HintTier::nth(self.tier);
+            self.tier += 1;
+            self.next_hint(tier, answer, known)
+        });
+        let Some(hint) = hint else {
+            outln!(io, "Nothing left to give away!");
+            return;
+        };
+
+        self.used += 1;
+        outln!(io, "Hint: {hint}");
+        outln!(io, "({} of {max} hints used)", self.used);
+    }
+
+    /// What the tier gives away about the answer, if anything.
+    fn next_hint(&mut self, tier: HintTier, answer: &str, known: &[bool]) -> Option<String> {
+        let letters: Vec<char> = answer.to_uppercase().chars().collect();
+        let hidden =
+            |i: &usize| !self.revealed.contains(i) && !known.get(*i).copied().unwrap_or(false);
+
+        match tier {
+            HintTier::Definition => {
+                let sense = define(answer)?.senses.into_iter().next()?;
+                Some(mask(&sense.to_string(), answer))
+            }
+            HintTier::FirstLetter => {
+                let first = *letters.first()?;
+                hidden(&0).then(|| {
+                    self.revealed.push(0);
+                    format!("It starts with {first}")
+                })
+            }
+            HintTier::Letter => {
+                let i = (0..letters.len()).find(hidden)?;
+                self.revealed.push(i);
+                Some(format!("Letter {} is {}", i + 1, letters[i]))
+            }
+            HintTier::Synonyms => {
+                let entry = define(answer)?;
+                match (entry.synonyms.is_empty(), entry.senses.get(1)) {
+                    (false, _) => Some(format!("Similar to {}", entry.synonyms.join(", "))),
+                    (true, Some(sense)) => Some(mask(&sense.to_string(), answer)),
+                    (true, None) => None,
+                }
+            }
+        }
+    }
+
+    /// Starts the tiers over for a new word (Anagrams hints at one word after
+    /// another). The hints already used still count towards the max.
+    pub fn next_word(&mut self) {
+        self.tier = 0;
+        self.revealed.clear();
+    }
+}
@@ -0,0 +1,99 @@
+use std::fmt;
+
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use unicode_normalization::{char::is_combining_mark, UnicodeNormalization};
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::WordList;
+
+/// The language a game is played in, picked with `--language` (or `language` in
+/// the config). Only English has embedded word lists, the others are played with
+/// a list of the player's own, see `Language::word_list`.
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Language {
+    #[default]
+    English,
+    /// Ñ is its own letter, other accents are dropped.
+    Spanish,
+    /// Ä, Ö and Ü are their own letters, ß is spelt SS and other accents are dropped.
+    German,
+    /// Accents are dropped, Œ and Æ are spelt OE and AE.
+    French,
+}
+
+impl Language {
+    /// Every letter words are made of, uppercase.
+    pub fn alphabet(&self) -> &'static str {
+        match self {
+            Language::English | Language::French => "ABCDEFGHIJKLMNOPQRSTUVWXYZ",
+            Language::Spanish => "ABCDEFGHIJKLMNÑOPQRSTUVWXYZ",
+            Language::German => "ABCDEFGHIJKLMNOPQRSTUVWXYZÄÖÜ",
+        }
+    }
+
+    /// The rows of letters on the language's usual keyboard.
+    pub fn keyboard(&self) -> [&'static str; 3] {
+        match self {
+            Language::English => ["QWERTYUIOP", "ASDFGHJKL", "ZXCVBNM"],
+            Language::Spanish => ["QWERTYUIOP", "ASDFGHJKLÑ", "ZXCVBNM"],
+            Language::German => ["QWERTZUIOPÜ", "ASDFGHJKLÖÄ", "YXCVBNM"],
+            Language::French => ["AZERTYUIOP", "QSDFGHJKLM", "WXCVBN"],
+        }
+    }
+
+    pub fn is_letter(&self, letter: char) -> bool {
+        self.alphabet().contains(letter)
+    }
+
+    /// Whether every letter of the (normalised) word is in the alphabet.
+    pub fn is_word(&self, word: &str) -> bool {
+        !word.is_empty() && word.chars().all(|c| self.is_letter(c))
+    }
+
+    /// Uppercases the text and folds any letters outside the alphabet into ones
+    /// in it, so `"straße"` is `"STRASSE"` in German and `"café"` is `"CAFE"`.
+    /// Letters are composed first, so every letter of the alphabet ends up a
+    /// single `char` and a word's letters can be compared char by char. Anything
+    /// that isn't a letter is left alone.
+    pub fn normalise(&self, text: &str) -> String {
+        text.nfc()
+            .flat_map(char::to_uppercase)
+            .fold(String::new(), |mut folded, c| {
+                match c {
+                    c if self.is_letter(c) || !c.is_alphabetic() => folded.push(c),
+                    'ẞ' => folded.push_str("SS"),
+                    'Œ' => folded.push_str("OE"),
+                    'Æ' => folded.push_str("AE"),
+                    c => folded.extend(c.nfd().filter(|c| !is_combining_mark(*c))),
+                }
+                folded
+            })
+    }
+
+    /// Where answers come from when no `--word-list` is given. Languages other
+    /// than English have no list of their own, so it's the one named after the
+    /// language in `WordList::lists_dir()`, e.g. `spanish.txt`.
+    pub fn word_list(&self) -> WordList {
+        match (self, WordList::lists_dir()) {
+            (Language::English, _) | (_, None) => WordList::default(),
+            (language, Some(dir)) => WordList::Custom(dir.join(format!("{language}.txt"))),
+        }
+    }
+}
+
+impl fmt::Display for Language {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.to_possible_value() {
+            Some(value) => write!(f, "{}", value.get_name()),
+            None => write!(f, "{self:?}"),
+        }
+    }
+}
+
+/// How many letters a word has as the player sees them, counting graphemes rather
+/// than bytes or chars.
+pub fn letter_count(word: &str) -> usize {
+    word.graphemes(true).count()
+}
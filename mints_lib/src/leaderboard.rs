@@ -0,0 +1,239 @@
+use std::{fmt, fs, io, path::PathBuf};
+
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    config, data_dir, Args, Difficulty, Game, GameResult, Mints, Outcome, WordList, DEFAULT_GUESSES,
+};
+
+/// How many entries each board keeps.
+pub const BOARD_SIZE: usize = 10;
+
+/// The best scores of every player sharing the file, stored as JSON in the mints
+/// data directory (or wherever `leaderboard` in the config points, so a team can
+/// share one). Kept separately for every game, letter count and difficulty, and
+/// for each daily puzzle and set of `Rules`, since only those rounds played the
+/// same words the same way.
+#[derive(Serialize, Deserialize, Default, Debug)]
+pub struct Leaderboard {
+    pub boards: Vec<Board>,
+}
+
+/// The leaderboard for a single mode.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Board {
+    pub game: Mints,
+    pub letters: i32,
+    pub difficulty: Difficulty,
+    /// The daily puzzle number, when playing `--daily`.
+    pub puzzle: Option<i64>,
+    pub rules: Rules,
+    /// Best first.
+    pub entries: Vec<BoardEntry>,
+}
+
+/// The settings a round was played with that change its words or what it can
+/// score, beyond its game, letters and difficulty.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Rules {
+    /// The `--seed`, or the daily puzzle's own seed when playing `--daily`.
+    pub seed: Option<u64>,
+    /// Wordle's `--guesses`, which more guesses left over score more for.
+    pub guesses: Option<i32>,
+    /// Wordle's `--hard-mode`.
+    pub hard_mode: bool,
+    /// The `--word-list`, by name or path.
+    pub word_list: String,
+    pub rarity: Option<f64>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct BoardEntry {
+    pub player: String,
+    pub score: u32,
+    pub guesses: i32,
+    pub hints_used: u32,
+    pub elapsed_ms: u64,
+    /// When the round was played, `YYYY-MM-DD`.
+    pub date: String,
+}
+
+impl Leaderboard {
+    /// Where the leaderboard is stored, `leaderboard` from the config or else
+    /// `$XDG_DATA_HOME/mints/leaderboard.json` on Linux.
+    pub fn path() -> Option<PathBuf> {
+        config()
+            .leaderboard
+            .clone()
+            .or_else(|| data_dir().map(|dir| dir.join("leaderboard.json")))
+    }
+
+    /// Loads the stored leaderboard, starting afresh if there isn't one (or it
+    /// can't be read).
+    pub fn load() -> Self {
+        Self::path()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        let path = Self::path().ok_or(io::ErrorKind::NotFound)?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+
+        fs::write(path, serde_json::to_string_pretty(self)?)
+    }
+
+    /// Adds a won round to its board, returning its place (from 1) if it made
+    /// the board. `rules` are the ones the round was played with. Rounds the
+    /// solver helped with don't count.
+    pub fn record(&mut self, result: &GameResult, rules: &Rules) -> Option<usize> {
+        if result.outcome != Outcome::Won || result.assisted {
+            return None;
+        }
+
+        let index = match self.boards.iter().position(|b| {
+            b.game == result.game
+                && b.letters == result.letters
+                && b.difficulty == result.difficulty
+                && b.puzzle == result.puzzle
+                && b.rules == *rules
+        }) {
+            Some(index) => index,
+            None => {
+                self.boards.push(Board {
+                    game: result.game,
+                    letters: result.letters,
+                    difficulty: result.difficulty.clone(),
+                    puzzle: result.puzzle,
+                    rules: rules.clone(),
+                    entries: Vec::new(),
+                });
+                self.boards.len() - 1
+            }
+        };
+
+        let entries = &mut self.boards[index].entries;
+        // Ties go to whoever got there first.
+        let place = entries.partition_point(|e| e.score >= result.score.total);
+        if place >= BOARD_SIZE {
+            return None;
+        }
+
+        entries.insert(
+            place,
+            BoardEntry {
+                player: player(),
+                score: result.score.total,
+                guesses: result.guesses,
+                hints_used: result.hints_used,
+                elapsed_ms: result.elapsed.as_millis() as u64,
+                date: Local::now().date_naive().to_string(),
+            },
+        );
+        entries.truncate(BOARD_SIZE);
+        Some(place + 1)
+    }
+
+    /// The boards of the game (or every game), daily puzzles only if `daily`.
+    pub fn boards(&self, game: Option<Mints>, daily: bool) -> Vec<&Board> {
+        self.boards
+            .iter()
+            .filter(|b| game.is_none_or(|game| b.game == game))
+            .filter(|b| !daily || b.puzzle.is_some())
+            .collect()
+    }
+}
+
+/// The name scores are recorded under, `player` from the config or else the
+/// user's login name.
+pub fn player() -> String {
+    config()
+        .player
+        .clone()
+        .or_else(|| std::env::var("USER").ok())
+        .or_else(|| std::env::var("USERNAME").ok())
+        .unwrap_or_else(|| "Player".to_string())
+}
+
+impl Rules {
+    /// The rules `args` play `G` by.
+    pub fn new<G: Game>(args: &Args) -> Self {
+        let wordle = G::GAME == Mints::Wordle;
+        Rules {
+            seed: args.round_seed::<G>(),
+            guesses: wordle.then_some(args.guesses),
+            hard_mode: wordle && args.hard_mode,
+            word_list: args.word_list.to_string(),
+            rarity: args.rarity,
+        }
+    }
+}
+
+/// Whatever isn't the default, e.g. ` (9 guesses, hard mode)`. The seed is left
+/// to the board.
+impl fmt::Display for Rules {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut rules = Vec::new();
+        if let Some(guesses) = self.guesses.filter(|&guesses| guesses != DEFAULT_GUESSES) {
+            rules.push(format!("{guesses} guesses"));
+        }
+        if self.hard_mode {
+            rules.push("hard mode".to_string());
+        }
+        if self.word_list != WordList::default().to_string() {
+            rules.push(format!("{} list", self.word_list));
+        }
+        if let Some(rarity) = self.rarity {
+            rules.push(format!("rarity {rarity}"));
+        }
+
+        match rules.is_empty() {
+            true => Ok(()),
+            false => write!(f, " ({})", rules.join(", ")),
+        }
+    }
+}
+
+impl fmt::Display for Board {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:?} ({} letters, {:?})",
+            self.game, self.letters, self.difficulty
+        )?;
+        match (self.puzzle, self.rules.seed) {
+            (Some(puzzle), _) => write!(f, " - Puzzle #{puzzle}")?,
+            (None, Some(seed)) => write!(f, " - Seed {seed}")?,
+            (None, None) => (),
+        }
+        writeln!(f, "{}", self.rules)?;
+
+        let width = self
+            .entries
+            .iter()
+            .map(|e| e.player.len())
+            .max()
+            .unwrap_or(0);
+        for (i, entry) in self.entries.iter().enumerate() {
+            let secs = entry.elapsed_ms / 1000;
+            writeln!(
+                f,
+                "  {:>2}. {:<width$}  {:>5}  {} guesses, {} hints, {}m {:02}s on {}",
+                i + 1,
+                entry.player,
+                entry.score,
+                entry.guesses,
+                entry.hints_used,
+                secs / 60,
+                secs % 60,
+                entry.date
+            )?;
+        }
+
+        Ok(())
+    }
+}
@@ -1,288 +1,646 @@
-use clap::{value_parser, Parser, ValueEnum};
+use chrono::{Local, NaiveDate};
+use clap::{
+    parser::ValueSource, value_parser, CommandFactory, FromArgMatches, Parser, Subcommand,
+    ValueEnum,
+};
 use colored::Colorize;
-use rand::seq::SliceRandom;
-use std::{collections::HashMap, error::Error, io::Write};
+use rand::{seq::SliceRandom, Rng, SeedableRng};
+use rand_chacha::ChaCha12Rng;
+use serde::{Deserialize, Serialize, Serializer};
+use std::{
+    error::Error,
+    io,
+    ops::{Range, RangeInclusive},
+    path::PathBuf,
+    time::Duration,
+};
+
+mod commands;
+mod config;
+mod console;
+mod definitions;
+mod dictionary;
+mod feedback;
+mod hints;
+mod language;
+mod leaderboard;
+mod saves;
+mod score;
+mod solver;
+mod stats;
+mod vocabulary;
+mod word_list;
+pub use commands::{handle_command, Command, Commands};
+pub use config::{config, Config, Theme};
+pub use console::Console;
+pub use definitions::{
+    mask, DefinitionSource, Definitions, Embedded, Entry, JsonDefinitions, PartOfSpeech, Sense,
+    WordNet,
+};
+pub use dictionary::Dictionary;
+pub use feedback::{hard_mode_violation, marks, score_guess, LetterResult};
+pub use hints::{HintTier, Hints};
+pub use language::{letter_count, Language};
+pub use leaderboard::{player, Board, BoardEntry, Leaderboard, Rules, BOARD_SIZE};
+pub use saves::{clear_save, load_game, save_game, save_path, started, Save};
+pub use score::{letter_value, word_value, Score, GUESS_BONUS, HINT_PENALTY, TIME_BONUS};
+pub use solver::{entropy, information, Solver, Suggestion, SAMPLE_SIZE};
+pub use stats::{ModeStats, Stats};
+pub use vocabulary::{AllowedGuesses, AnswerPool, Strictness};
+pub use word_list::{CustomList, Rejection, WordList};
+
+/// A clutter-helper in the fashion of `print!()` that writes to the given output
+/// (usually a `Console`) instead of stdout.
+#[macro_export]
+macro_rules! out {
+    ($out:expr, $($arg:tt)*) => {{
+        use std::io::Write as _;
+        $out.write_fmt(format_args!($($arg)*)).expect("Failed to write output");
+    }};
+}
+
+/// A clutter-helper in the fashion of `println!()` that writes to the given output
+/// (usually a `Console`) instead of stdout.
+#[macro_export]
+macro_rules! outln {
+    ($out:expr) => {
+        $crate::out!($out, "\n")
+    };
+    ($out:expr, $($arg:tt)*) => {
+        $crate::out!($out, "{}\n", format_args!($($arg)*))
+    };
+}
 
-/// A clutter-helper to avoid repeated `println!()`. Specify the number of newlines
+/// A clutter-helper to avoid repeated `outln!()`. Specify the number of newlines
 /// desired or omit for a single one.
 #[macro_export]
 macro_rules! newln {
-    ($repeat:expr) => {
+    ($out:expr, $repeat:expr) => {
         for _ in 0..$repeat {
-            println!();
+            $crate::outln!($out);
         }
     };
-    () => {
-        println!();
+    ($out:expr) => {
+        $crate::outln!($out);
     };
 }
 
-/// A clutter helper to sleep for a specified amount of time (milliseconds)
+/// A clutter helper to sleep for a specified amount of time (milliseconds).
+/// Headless consoles don't sleep at all.
 #[macro_export]
 macro_rules! sleep {
-    ($millis:expr) => {
-        std::thread::sleep(std::time::Duration::from_millis($millis));
+    ($out:expr, $millis:expr) => {
+        $out.sleep($millis)
     };
 }
 
-/// A clutter helper to flush the stdout on call
+/// A clutter helper to flush the given output on call
 #[macro_export]
 macro_rules! flush {
-    () => {
-        std::io::stdout().flush().expect("Failed to flush stdout");
-    };
+    ($out:expr) => {{
+        use std::io::Write as _;
+        $out.flush().expect("Failed to flush output");
+    }};
 }
 
-/// A clutter helper to clear the terminal screen
+/// A clutter helper to clear the terminal screen. Headless consoles are never
+/// cleared.
 #[macro_export]
 macro_rules! clear {
-    () => {
-        print!("{}[2J", 27 as char);
+    ($out:expr) => {
+        if !$out.is_headless() {
+            $crate::out!($out, "{}[2J", 27 as char);
+        }
     };
 }
 
-/// Used to signify to the `mint_cli` to continue the game loop.
-pub const GAME_ONGOING: i32 = 31;
-
-/// Used to signify to the `mint_cli`to restart the game loop.
-pub const GAME_RESTART: i32 = 32;
-
-/// Used to signify to the `mint_cli` to break the game loop.
-pub const GAME_OVER: i32 = 33;
-
-const DEFAULT_LIST: ListType = ListType::Gpt;
-
-// These are all added as const strings as to be embedded in the binary.
-// Any word lists to be added need to be embedded in the binary to be read
-// by the read_word_list function.
-pub const WORDS_4E: &str = include_str!("../word_lists/4E.txt");
-pub const WORDS_4M: &str = include_str!("../word_lists/4M.txt");
-pub const WORDS_4H: &str = include_str!("../word_lists/4H.txt");
-pub const WORDS_5E: &str = include_str!("../word_lists/5E.txt");
-pub const WORDS_5M: &str = include_str!("../word_lists/5M.txt");
-pub const WORDS_5H: &str = include_str!("../word_lists/5H.txt");
-pub const WORDS_6E: &str = include_str!("../word_lists/6E.txt");
-pub const WORDS_6M: &str = include_str!("../word_lists/6M.txt");
-pub const WORDS_6H: &str = include_str!("../word_lists/6H.txt");
-pub const WORDS_7E: &str = include_str!("../word_lists/7E.txt");
-pub const WORDS_7M: &str = include_str!("../word_lists/7M.txt");
-pub const WORDS_7H: &str = include_str!("../word_lists/7H.txt");
-pub const WORDS_8E: &str = include_str!("../word_lists/8E.txt");
-pub const WORDS_8M: &str = include_str!("../word_lists/8M.txt");
-pub const WORDS_8H: &str = include_str!("../word_lists/8H.txt");
+/// How a round of a game came to an end.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Outcome {
+    Won,
+    Lost,
+    /// The player quit or restarted before the round was decided.
+    Abandoned,
+}
+
+/// A single accepted guess and the feedback the game gave for it.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Guess {
+    pub guess: String,
+
+    /// Game specific, e.g. `G`/`Y`/`-` per letter in Wordle or `hit`/`miss` in Hangman.
+    pub feedback: String,
+}
+
+/// A summary of a finished round, handed back to the `mint_cli` (or anything
+/// else embedding `mints_lib`) so it can react to *how* the round ended.
+#[derive(Clone, Debug, Serialize)]
+pub struct GameResult {
+    pub game: Mints,
+    pub letters: i32,
+    pub difficulty: Difficulty,
+    pub outcome: Outcome,
+    pub answer: String,
+    pub guesses: i32,
+    pub history: Vec<Guess>,
+    /// How many `!hint`s the player took.
+    pub hints_used: u32,
+    /// Whether the player had help from the solver (`--assist` or `!suggest`).
+    pub assisted: bool,
+    pub score: Score,
+    /// The daily puzzle number, when playing `--daily`.
+    pub puzzle: Option<i64>,
+    #[serde(rename = "elapsed_ms", serialize_with = "serialize_millis")]
+    pub elapsed: Duration,
+}
+
+impl GameResult {
+    /// Whether anything actually happened in the round. Rounds abandoned before
+    /// a single guess (e.g. the input running out) weren't really played.
+    pub fn was_played(&self) -> bool {
+        self.outcome != Outcome::Abandoned || !self.history.is_empty()
+    }
+}
+
+fn serialize_millis<S: Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_u128(duration.as_millis())
+}
+
+/// Returned by `Game::do_loop` to tell the `mint_cli` what to do next.
+#[derive(Clone, Debug)]
+pub enum LoopOutcome {
+    /// The round is still in progress, keep looping.
+    Ongoing,
+    /// The round ended and a new one should be started.
+    Restart(GameResult),
+    /// The round ended and the game should be finished.
+    Over(GameResult),
+}
+
+const DEFAULT_LIST: ListType = ListType::Frequency;
+
+/// The shortest and longest words the frequency list has enough of to play with.
+/// The GPT lists only go from 4 to 8.
+pub const MIN_LETTERS: i32 = 3;
+pub const MAX_LETTERS: i32 = 12;
+
+/// Wordle's guesses when neither `--guesses` nor the config say otherwise.
+pub const DEFAULT_GUESSES: i32 = 6;
+
+/// How far either side of `--rarity` answers are picked from.
+const RARITY_SPREAD: f64 = 0.1;
+
+/// The day of daily puzzle #1, every day after counts up from here.
+const DAILY_EPOCH: NaiveDate = match NaiveDate::from_ymd_opt(2024, 1, 1) {
+    Some(date) => date,
+    None => panic!("Invalid daily epoch"),
+};
 
 /// This is the `words_alpha.txt` list from the [dwyl/english_words](https://github.com/dwyl/english-words/blob/master/words_alpha.txt)
 /// Github repository. It contains all alpha words. Used to check if a guessed
 /// word actually exists... Considering the size of the list, most guesses probably do...
 pub const WORDS_MASTER: &str = include_str!("../word_lists/Master.txt");
 
-/// Default methods for a terminal-based game.
+/// Default methods for a terminal-based game. All input is read from, and all
+/// output written to, the `Console` handed in by the caller.
 pub trait Game {
+    /// Which of the games this is.
+    const GAME: Mints;
+
     /// Used to transform the arguments, if any, into the game object.
     fn new(args: &Args) -> Self;
 
     /// Starts the game. This is called **once** at the beginning by the
     /// `mint_cli`.
-    fn start(&mut self);
+    fn start(&mut self, io: &mut Console);
 
     /// What should be done in a loop. This is called in a loop by the
-    /// `mint_cli` until an error occurs, or a code other than `GAME_ONGOING`
-    /// is returned.
-    fn do_loop(&mut self) -> Result<i32, Box<dyn Error>>;
+    /// `mint_cli` until an error occurs, or anything other than
+    /// `LoopOutcome::Ongoing` is returned.
+    fn do_loop(&mut self, io: &mut Console) -> Result<LoopOutcome, Box<dyn Error>>;
+
+    /// The lengths of words the game needs from its word list.
+    fn word_lengths(args: &Args) -> RangeInclusive<usize>;
+
+    /// Summarises the current round with the given outcome.
+    fn result(&self, outcome: Outcome) -> GameResult;
+
+    /// How many seconds `!restart` counts down before the next round.
+    const RESTART_COUNTDOWN: u64 = 3;
+
+    /// The `!commands` understood by the game. Games with commands of their own
+    /// register them on top of the common ones and handle them in `run_command`.
+    fn commands(&self) -> Commands {
+        Commands::default()
+    }
+
+    /// Gives the player a hint, called for `!hint`.
+    fn hint(&mut self, io: &mut Console);
+
+    /// Shows the player what they missed before a `!restart`.
+    fn reveal(&mut self, io: &mut Console);
+
+    /// Runs one of the game's own commands (by name) registered in `commands`.
+    fn run_command(&mut self, io: &mut Console, name: &str) -> Result<LoopOutcome, Box<dyn Error>> {
+        let _ = name;
+        outln!(io, "Unknown command!");
+        Ok(LoopOutcome::Ongoing)
+    }
 
     /// Finishes the game. Anything to be cleaned up/done **once** at the end
     /// of the game loop should be done here.
-    fn finish(self);
+    fn finish(self, io: &mut Console);
 }
 
-#[allow(dead_code)]
-enum ListType {
+/// Which of the embedded lists answers come from, see `build.rs`.
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ListType {
+    /// Words ranked by how common they are, each difficulty a band of them.
+    Frequency,
+    /// Every word of the GPT generated lists.
     Gpt,
+    /// Only the words of the GPT generated lists that Webster defines.
     Webster,
 }
 
-#[derive(ValueEnum, Clone, Debug)]
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum Mints {
     Wordle,
     Hangman,
     Anagrams,
 }
 
-#[derive(ValueEnum, Clone, Debug)]
+#[derive(ValueEnum, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum Difficulty {
     Easy,
     Medium,
     Hard,
 }
 
-#[derive(Parser, Debug)]
+impl Difficulty {
+    /// The band of the frequency list the difficulty's answers come from, `0.0`
+    /// being the most common word and `1.0` the rarest. The bands don't overlap.
+    pub fn rarity(&self) -> Range<f64> {
+        match self {
+            Difficulty::Easy => 0.0..1.0 / 3.0,
+            Difficulty::Medium => 1.0 / 3.0..2.0 / 3.0,
+            Difficulty::Hard => 2.0 / 3.0..1.0,
+        }
+    }
+
+    /// What a round's `Score` is multiplied by.
+    pub fn multiplier(&self) -> f64 {
+        match self {
+            Difficulty::Easy => 1.0,
+            Difficulty::Medium => 1.5,
+            Difficulty::Hard => 2.0,
+        }
+    }
+}
+
+// Things to do other than playing a game.
+#[derive(Subcommand, Clone, Debug)]
+pub enum Action {
+    /// Show your stats for every game played.
+    Stats,
+    /// Show the best scores, of everyone sharing the leaderboard.
+    Leaderboard {
+        #[arg(help = "Only show this game's boards.")]
+        game: Option<Mints>,
+
+        #[arg(long = "daily", help = "Only show the boards of daily puzzles.")]
+        daily: bool,
+    },
+}
+
+#[derive(Parser, Clone, Debug)]
+#[command(name = "mints", args_conflicts_with_subcommands = true)]
 pub struct Args {
-    #[arg(help = "The game to play.")]
-    pub game: Mints,
+    #[command(subcommand)]
+    pub action: Option<Action>,
+
+    #[arg(help = "The game to play. Opens the menu when left out.")]
+    pub game: Option<Mints>,
 
-    #[arg(short = 'g', long = "guesses", default_value_t = 6, value_parser = value_parser!(i32).range(3..=9))]
+    #[arg(short = 'g', long = "guesses", default_value_t = DEFAULT_GUESSES, value_parser = value_parser!(i32).range(3..=9))]
     pub guesses: i32,
 
-    #[arg(short = 'l', long = "letters", default_value_t = 5, value_parser = value_parser!(i32).range(4..=8))]
+    #[arg(short = 'l', long = "letters", default_value_t = 5, value_parser = value_parser!(i32).range(MIN_LETTERS as i64..=MAX_LETTERS as i64))]
     pub letters: i32,
 
     #[arg(short = 'd', long = "difficulty", default_value_t = Difficulty::Easy)]
     #[clap(value_enum)]
     pub difficulty: Difficulty,
+
+    #[arg(
+        long = "word-list",
+        default_value_t = WordList::default(),
+        help = "Where answers come from: frequency, gpt, webster (only the GPT words Webster defines), a file of your own words, or the name of a list in ~/.config/mints/lists."
+    )]
+    pub word_list: WordList,
+
+    #[arg(
+        long = "language",
+        default_value_t = Language::English,
+        help = "The language to play in. Only English has lists built in, others default to the list named after them in ~/.config/mints/lists."
+    )]
+    #[clap(value_enum)]
+    pub language: Language,
+
+    #[arg(
+        long = "rarity",
+        value_parser = parse_rarity,
+        help = "How rare the answers are, from 0.0 (the most common words) to 1.0 (the rarest). Overrides the difficulty's band of the frequency list."
+    )]
+    pub rarity: Option<f64>,
+
+    #[arg(
+        long = "strictness",
+        help = "Which guesses to take: strict (only the curated lists), standard (any dictionary word) or free (any letters)."
+    )]
+    #[clap(value_enum)]
+    pub strictness: Option<Strictness>,
+
+    #[arg(
+        long = "hard-mode",
+        help = "Wordle: letters found in the right spot have to stay there, and letters found elsewhere have to be used again."
+    )]
+    pub hard_mode: bool,
+
+    #[arg(
+        long = "assist",
+        help = "Wordle: show the answers still possible and the most informative next guesses after every guess. Assisted rounds don't go on the leaderboard."
+    )]
+    pub assist: bool,
+
+    #[arg(
+        long = "seed",
+        help = "Seed for picking words and scrambles. Each following round uses the next seed."
+    )]
+    pub seed: Option<u64>,
+
+    #[arg(
+        long = "daily",
+        conflicts_with = "seed",
+        help = "Play today's puzzle, the same for everyone playing the game with the same word length and difficulty."
+    )]
+    pub daily: bool,
+
+    #[arg(
+        long = "resume",
+        requires = "game",
+        conflicts_with_all = ["seed", "daily"],
+        help = "Pick the game's unfinished round back up, with the options it was started with."
+    )]
+    pub resume: bool,
+
+    #[arg(
+        long = "headless",
+        help = "Read guesses line by line without animations, printing a JSON result per round."
+    )]
+    pub headless: bool,
 }
 
-pub fn choose_random_word(words: &[String]) -> String {
-    let mut rng = rand::thread_rng();
-    words
-        .choose(&mut rng)
-        .cloned()
-        .expect("Failed to pick random word")
+/// Shows the round's summary and asks whether to play another once the current
+/// one is over, turning the answer into the matching `LoopOutcome`. Headless
+/// consoles always move on to the next round, the input running out is what
+/// finishes the game.
+pub fn play_again(io: &mut Console, result: GameResult) -> io::Result<LoopOutcome> {
+    if io.is_headless() {
+        return Ok(LoopOutcome::Restart(result));
+    }
+
+    summary(io, &result);
+
+    outln!(io, "Play again? (y/n)");
+    Ok(match io.read_line()? {
+        Some(s) if s.eq_ignore_ascii_case("y") => LoopOutcome::Restart(result),
+        _ => LoopOutcome::Over(result),
+    })
 }
 
-pub fn load_word_list(letters: i32, diff: &Difficulty) -> Vec<String> {
-    let txt = match letters {
-        4 => match diff {
-            Difficulty::Easy => WORDS_4E,
-            Difficulty::Medium => WORDS_4M,
-            Difficulty::Hard => WORDS_4H,
-        },
-        5 => match diff {
-            Difficulty::Easy => WORDS_5E,
-            Difficulty::Medium => WORDS_5M,
-            Difficulty::Hard => WORDS_5H,
-        },
-        6 => match diff {
-            Difficulty::Easy => WORDS_6E,
-            Difficulty::Medium => WORDS_6M,
-            Difficulty::Hard => WORDS_6H,
-        },
-        7 => match diff {
-            Difficulty::Easy => WORDS_7E,
-            Difficulty::Medium => WORDS_7M,
-            Difficulty::Hard => WORDS_7H,
-        },
-        8 => match diff {
-            Difficulty::Easy => WORDS_8E,
-            Difficulty::Medium => WORDS_8M,
-            Difficulty::Hard => WORDS_8H,
-        },
-        _ => unreachable!(),
+/// How the round went and what it scored, shown once it's over.
+fn summary(io: &mut Console, result: &GameResult) {
+    let heading = match result.outcome {
+        Outcome::Won => "You won!",
+        Outcome::Lost => "Better luck next time!",
+        Outcome::Abandoned => "Round over",
     };
 
-    let words = sanitise_gpt_list(txt, letters);
-    words
+    newln!(io);
+    outln!(io, "{}", format!(" {heading} ").bold());
+    outln!(io, "  Answer          {:>5}", result.answer);
+    outln!(io, "  Guesses         {:>5}", result.guesses);
+    outln!(
+        io,
+        "  Time           {:>5.1}s",
+        result.elapsed.as_secs_f64()
+    );
+    outln!(io, "{}", "  -----------------------".dimmed());
+    outln!(io, "{}", result.score);
+    newln!(io);
 }
 
-/// As the word lists are generated by GPT-3.5, this function takes the list generated
-/// online using OpenAI's website.
-///
-/// ## Prompt
-///
-/// > "ok im doing a word list for wordle, lets start with <4/5/6/7/8> letters, <easy/medium/hard>/
-///    <common/uncommon/rare> words, 300 words in copyable code block and try your absolute
-///    hardest to NOT repeat words"
-///
-/// Once copied into a txt file, this function captures repeats and makes sure each word
-/// is the specified letter amount. It also turns everything to uppercase for use in the game.
-///
-/// You have two options for santisation here, you can accept all words that meet the above
-/// criteria, or you can accept only the words which exist in the webster dictionary (70-80%).
-/// The dictionary lookup is done by the `webster` crate. Change the `DEFAULT_LIST` parameter
-/// to change which words to accept. Webster doesn't have *all* words, but the GPT list won't
-/// guarantee actual words. Choice is yours. GPT is set by default as it tends to be alright.
-///
-/// ## Word Counts
-///
-/// | Difficulty | GPT Count | Webster Count |
-/// |------------|-----------|---------------|
-/// | 4E         | 234       | 198           |
-/// | 4M         | 328       | 263           |
-/// | 4H         | 294       | 142           |
-/// | 5E         | 286       | 229           |
-/// | 5M         | 331       | 264           |
-/// | 5H         | 489       | 259           |
-/// | 6E         | 232       | 172           |
-/// | 6M         | 211       | 156           |
-/// | 6H         | 231       | 148           |
-/// | 7E         | 329       | 153           |
-/// | 7M         | 247       | 131           |
-/// | 7H         | 322       | 137           |
-/// | 8E         | 112       | 74            |
-/// | 8M         | 184       | 80            |
-/// | 8H         | 122       | 76            |
-///
-/// ## Overlap Counts
-/// | Letters | All Difficulties | Easy & Medium | Easy & Difficult | Medium & Difficult |
-/// |---------|------------------|---------------|------------------|--------------------|
-/// | 4       | 1                | 23            | 8                | 26                 |
-/// | 5       | 29               | 147           | 30               | 158                |
-/// | 6       | 10               | 51            | 16               | 36                 |
-/// | 7       | 12               | 64            | 26               | 44                 |
-/// | 8       | 0                | 19            | 0                | 1                  |
-///
-/// Because these are all generated with ChatGPT, it doesn't always isolate
-/// words to the difficulty categories.
-///
-fn sanitise_gpt_list(list: &str, letters: i32) -> Vec<String> {
-    let mut repeat_map = HashMap::new();
-
-    for line in list.lines() {
-        for word in line.split_ascii_whitespace().map(|s| s.trim()) {
-            if word.len() == letters as usize && word.chars().all(|c| c.is_ascii_alphabetic()) {
-                *repeat_map.entry(word.to_ascii_uppercase()).or_insert(0) += 1;
-            }
+impl Args {
+    /// Parses the command line, taking any game options it leaves out from the
+    /// config rather than the built-in defaults.
+    pub fn parse_with(config: &Config) -> Self {
+        let matches = Self::command().get_matches();
+        let mut args = Self::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
+        let given = |id| matches.value_source(id) == Some(ValueSource::CommandLine);
+
+        if let (false, Some(guesses)) = (given("guesses"), config.guesses) {
+            args.guesses = guesses;
+        }
+        if let (false, Some(letters)) = (given("letters"), config.letters) {
+            args.letters = letters;
+        }
+        if let (false, Some(difficulty)) = (given("difficulty"), &config.difficulty) {
+            args.difficulty = difficulty.clone();
+        }
+        if let (false, Some(language)) = (given("language"), config.language) {
+            args.language = language;
+        }
+        if !given("word_list") {
+            args.word_list = config
+                .word_list
+                .clone()
+                .unwrap_or_else(|| args.language.word_list());
+        }
+
+        if args.language != Language::English && matches!(args.word_list, WordList::Builtin(_)) {
+            Self::command()
+                .error(
+                    clap::error::ErrorKind::ArgumentConflict,
+                    format!(
+                        "The built in word lists are English only, --word-list needs a {} list",
+                        args.language
+                    ),
+                )
+                .exit();
+        }
+
+        if args.rarity.is_some() && args.word_list != WordList::Builtin(ListType::Frequency) {
+            Self::command()
+                .error(
+                    clap::error::ErrorKind::ArgumentConflict,
+                    "--rarity only works with the frequency word list",
+                )
+                .exit();
+        }
+
+        args
+    }
+
+    /// The band of the frequency list to pick answers from, around `--rarity` if
+    /// given, otherwise the difficulty's.
+    pub fn rarity(&self) -> Range<f64> {
+        match self.rarity {
+            Some(rarity) => (rarity - RARITY_SPREAD).max(0.0)..(rarity + RARITY_SPREAD).min(1.0),
+            None => self.difficulty.rarity(),
         }
     }
 
-    let mut webster_list = Vec::new();
+    /// The RNG a game should draw all of its randomness from. Seeded from `--seed`
+    /// (or today's puzzle) when given so rounds can be reproduced, otherwise from
+    /// entropy.
+    /// It's the same ChaCha generator as `StdRng`, but one that can be saved
+    /// along with the round.
+    pub fn rng<G: Game>(&self) -> ChaCha12Rng {
+        match self.round_seed::<G>() {
+            Some(seed) => ChaCha12Rng::seed_from_u64(seed),
+            None => ChaCha12Rng::from_entropy(),
+        }
+    }
 
-    for word in repeat_map.keys() {
-        if webster::dictionary(word).is_some() {
-            webster_list.push(word.clone());
+    /// The seed `G`'s round is played with: today's puzzle when playing `--daily`,
+    /// otherwise `--seed`, if given.
+    pub fn round_seed<G: Game>(&self) -> Option<u64> {
+        match self.puzzle_number() {
+            Some(puzzle) => Some(self.daily_seed::<G>(puzzle)),
+            None => self.seed,
         }
     }
 
-    // dbg!(&repeat_map);
-    // dbg!(&webster_list);
-    // println!("Unique words: {}", repeat_map.keys().len());
-    // println!("Defined words: {}", webster_list.len());
-    // panic!();
+    /// The strictness given for the game, on the command line or in the config.
+    /// Left to the game's own default otherwise.
+    pub fn strictness(&self) -> Option<Strictness> {
+        self.strictness.or_else(|| {
+            self.game
+                .and_then(|game| config().strictness.get(&game).copied())
+        })
+    }
+
+    /// The number of today's daily puzzle (by local date), if playing `--daily`.
+    pub fn puzzle_number(&self) -> Option<i64> {
+        self.daily
+            .then(|| (Local::now().date_naive() - DAILY_EPOCH).num_days() + 1)
+    }
+
+    /// Mixes the puzzle number with the game, the length of word it plays and the
+    /// difficulty so each combination gets its own daily puzzle.
+    fn daily_seed<G: Game>(&self, puzzle: i64) -> u64 {
+        (puzzle as u64) * 10_000
+            + (G::GAME as u64) * 1000
+            + (*G::word_lengths(self).end() as u64) * 10
+            + self.difficulty.clone() as u64
+    }
+
+    /// Moves the arguments on to the next round, stepping the seed (if any) so
+    /// that a seeded session still plays a different word each round.
+    pub fn next_round(&mut self) {
+        self.seed = self.seed.map(|seed| seed.wrapping_add(1));
+    }
+}
+
+fn parse_rarity(s: &str) -> Result<f64, String> {
+    let rarity: f64 = s.parse().map_err(|_| format!("{s} isn't a number"))?;
+    match (0.0..=1.0).contains(&rarity) {
+        true => Ok(rarity),
+        false => Err("rarity must be between 0.0 and 1.0".to_string()),
+    }
+}
 
-    let gpt_list: Vec<String> = repeat_map.keys().map(|s| s.to_owned()).collect();
+pub fn choose_random_word(words: &[String], rng: &mut impl Rng) -> String {
+    words
+        .choose(rng)
+        .cloned()
+        .expect("Failed to pick random word")
+}
 
-    match DEFAULT_LIST {
-        ListType::Gpt => gpt_list,
-        ListType::Webster => webster_list,
+/// The answers for the given letters and difficulty, from the list picked with
+/// `--word-list`. See `build.rs` for how the built in lists are sanitised. Custom
+/// lists have no difficulties, and any that can't be read are left empty (they're
+/// checked with `WordList::check` before a game starts) and are read in the given
+/// language.
+pub fn load_word_list(
+    letters: i32,
+    diff: &Difficulty,
+    list: &WordList,
+    language: Language,
+) -> Vec<String> {
+    match list {
+        WordList::Builtin(ListType::Frequency) => frequency_band(letters, diff.rarity()),
+        // Already sorted, so that a seeded RNG always picks the same word.
+        WordList::Builtin(list) => sanitised_list(letters, diff, *list)
+            .iter()
+            .map(|word| word.to_string())
+            .collect(),
+        WordList::Custom(path) => {
+            let letters = letters as usize;
+            WordList::load_custom(path, letters..=letters, language)
+                .map(|list| list.words.clone())
+                .unwrap_or_default()
+        }
     }
 }
 
-pub fn word_exists(letters: i32, word: &String) -> bool {
-    sanitise_gpt_list(WORDS_MASTER, letters).contains(word)
-        || load_word_list(letters, &Difficulty::Easy).contains(word)
-        || load_word_list(letters, &Difficulty::Medium).contains(word)
-        || load_word_list(letters, &Difficulty::Hard).contains(word)
+/// The `letters`-letter words of the frequency list within the `rarity` band.
+/// There's always at least one word, as long as there are any of the length.
+pub fn frequency_band(letters: i32, rarity: Range<f64>) -> Vec<String> {
+    let words = frequency_list(letters);
+    let len = words.len() as f64;
+    let end = ((rarity.end * len).ceil() as usize).min(words.len());
+    let start = ((rarity.start * len).floor() as usize).min(end.saturating_sub(1));
+
+    words[start..end]
+        .iter()
+        .map(|word| word.to_string())
+        .collect()
+}
+
+include!(concat!(env!("OUT_DIR"), "/word_lists.rs"));
+
+/// Looks the word up in the shared `Definitions`.
+pub fn define(word: &str) -> Option<Entry> {
+    Definitions::get().lookup(word)
 }
 
-pub fn define(word: &String) -> String {
-    webster::dictionary(word)
-        .unwrap_or("No definition found!")
-        .to_string()
+/// The word's first sense, with the word (and any of its inflections) masked.
+pub fn hint(word: &str) -> String {
+    match define(word).and_then(|entry| entry.senses.into_iter().next()) {
+        Some(sense) => mask(&sense.to_string(), word),
+        None => "No definition found!".to_string(),
+    }
 }
 
-pub fn hint(word: &String) -> String {
-    let word = word.to_ascii_lowercase();
-    let definition = define(&word);
-    let filler = "_".repeat(word.len());
-    definition.replace(&word, &format!("[{filler}]"))
+/// Where mints keeps everything it stores between sessions,
+/// `$XDG_DATA_HOME/mints` on Linux.
+pub fn data_dir() -> Option<PathBuf> {
+    dirs::data_dir().map(|dir| dir.join("mints"))
 }
 
 /// Prints a given header in block form, in the fashion of a pixel-game loading screen.
 /// Specify the desired loading time by passing in `ms` in milliseconds. This function will
 /// **not** clear the terminal after it finishes, leaving it to the game to handle when the loading
 /// screen should clear.
-pub fn titled_loading_screen(header: &str, color: &str, ms: usize) {
+pub fn titled_loading_screen(io: &mut Console, header: &str, color: &str, ms: usize) {
     let middle = if let Some((_, terminal_size::Height(h))) = terminal_size::terminal_size() {
-        (h as usize / 2) - 3
+        (h as usize / 2).saturating_sub(3)
     } else {
         0 // Default height in case terminal size can't be determined
     };
@@ -290,22 +648,24 @@ pub fn titled_loading_screen(header: &str, color: &str, ms: usize) {
     let hold_time = ms / 4;
     let print_time = ((ms / 4) * 3) / header.len();
 
-    clear!();
-    for (i, _) in header.chars().enumerate() {
-        print!(
+    clear!(io);
+    for (i, c) in header.char_indices() {
+        let end = i + c.len_utf8();
+        out!(
+            io,
             "{}",
-            terminal_fonts::to_block_string(&header[0..=i]).color(color)
+            terminal_fonts::to_block_string(&header[..end]).color(color)
         );
-        newln!(middle);
-        flush!();
+        newln!(io, middle);
+        flush!(io);
 
-        if i == header.len() - 1 {
-            sleep!(hold_time as u64);
+        if end == header.len() {
+            sleep!(io, hold_time as u64);
         } else {
-            sleep!(print_time as u64);
-            clear!();
+            sleep!(io, print_time as u64);
+            clear!(io);
         }
-        newln!();
+        newln!(io);
     }
 }
 
@@ -0,0 +1,74 @@
+use std::{fs, io, path::PathBuf};
+
+use clap::ValueEnum;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::{data_dir, Game, Mints, Rules};
+
+/// Where the unfinished round of `game` is kept, one per game, e.g.
+/// `$XDG_DATA_HOME/mints/saves/wordle.json` on Linux.
+pub fn save_path(game: Mints) -> Option<PathBuf> {
+    let name = game.to_possible_value()?.get_name().to_string();
+    data_dir().map(|dir| dir.join("saves").join(format!("{name}.json")))
+}
+
+/// A round saved for `--resume`, along with the rules (and seed) it was played
+/// with so it's still recorded under them once it's finished.
+#[derive(Serialize, Deserialize)]
+pub struct Save<G> {
+    pub rules: Rules,
+    pub game: G,
+}
+
+/// Saves the round in progress so it can be picked back up with `--resume`,
+/// replacing whatever was saved for the game before.
+pub fn save_game<G: Game + Serialize>(game: &G, rules: &Rules) -> io::Result<()> {
+    let path = save_path(G::GAME).ok_or(io::ErrorKind::NotFound)?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+
+    let save = Save {
+        rules: rules.clone(),
+        game,
+    };
+    fs::write(path, serde_json::to_string(&save)?)
+}
+
+/// Loads the round saved for `G`, if there is one (and it can be read).
+pub fn load_game<G: Game + DeserializeOwned>() -> Option<Save<G>> {
+    let json = fs::read_to_string(save_path(G::GAME)?).ok()?;
+    serde_json::from_str(&json).ok()
+}
+
+/// Removes the saved round of `G` once it's been finished.
+pub fn clear_save<G: Game>() -> io::Result<()> {
+    let Some(path) = save_path(G::GAME) else {
+        return Ok(());
+    };
+
+    match fs::remove_file(path) {
+        Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+        _ => Ok(()),
+    }
+}
+
+/// (De)serialises a round's `Instant` as the time elapsed since, for use with
+/// `#[serde(with = "mints_lib::started")]`. The clock picks up where it left off
+/// on resume rather than counting the time spent away.
+pub mod started {
+    use std::time::{Duration, Instant};
+
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(started: &Instant, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u64(started.elapsed().as_millis() as u64)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Instant, D::Error> {
+        let elapsed = Duration::from_millis(u64::deserialize(deserializer)?);
+        Ok(Instant::now()
+            .checked_sub(elapsed)
+            .unwrap_or_else(Instant::now))
+    }
+}
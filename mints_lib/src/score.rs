@@ -0,0 +1,95 @@
+use std::{fmt, time::Duration};
+
+use serde::Serialize;
+
+use crate::Difficulty;
+
+/// Points for each guess the player had left over.
+pub const GUESS_BONUS: u32 = 10;
+
+/// Points for an instant win, one less for every second taken.
+pub const TIME_BONUS: u32 = 100;
+
+/// Points taken off for each `!hint`.
+pub const HINT_PENALTY: u32 = 15;
+
+/// The points a round was worth. Every game scores the same way, the letters of
+/// the words solved plus any bonuses, less the hints taken, multiplied by the
+/// difficulty. Rounds that weren't won only keep the points for their words.
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct Score {
+    /// The Scrabble values of the letters of the words solved.
+    pub letters: u32,
+    pub guesses: u32,
+    pub time: u32,
+    /// Taken off rather than added.
+    pub hints: u32,
+    pub multiplier: f64,
+    pub total: u32,
+}
+
+impl Score {
+    /// The score for solving the words, before any bonuses.
+    pub fn new<S: AsRef<str>>(words: &[S], difficulty: &Difficulty, hints_used: u32) -> Self {
+        Score {
+            letters: words.iter().map(|word| word_value(word.as_ref())).sum(),
+            hints: hints_used * HINT_PENALTY,
+            multiplier: difficulty.multiplier(),
+            ..Default::default()
+        }
+        .tally()
+    }
+
+    /// Adds the bonus for the guesses left over.
+    pub fn guesses_left(mut self, guesses: u32) -> Self {
+        self.guesses = guesses * GUESS_BONUS;
+        self.tally()
+    }
+
+    /// Adds the bonus for how quickly the round was won.
+    pub fn in_time(mut self, elapsed: Duration) -> Self {
+        self.time = TIME_BONUS.saturating_sub(elapsed.as_secs() as u32);
+        self.tally()
+    }
+
+    fn tally(mut self) -> Self {
+        let points = (self.letters + self.guesses + self.time).saturating_sub(self.hints);
+        self.total = (points as f64 * self.multiplier).round() as u32;
+        self
+    }
+}
+
+/// The Scrabble value of a letter. Letters outside English are valued as in the
+/// Spanish and German sets, anything else is worth a point.
+pub fn letter_value(letter: char) -> u32 {
+    match letter.to_ascii_uppercase() {
+        'A' | 'E' | 'I' | 'L' | 'N' | 'O' | 'R' | 'S' | 'T' | 'U' => 1,
+        'D' | 'G' => 2,
+        'B' | 'C' | 'M' | 'P' => 3,
+        'F' | 'H' | 'V' | 'W' | 'Y' => 4,
+        'K' => 5,
+        'Ä' | 'ä' | 'Ü' | 'ü' => 6,
+        'J' | 'X' | 'Ñ' | 'ñ' | 'Ö' | 'ö' => 8,
+        'Q' | 'Z' => 10,
+        _ => 1,
+    }
+}
+
+pub fn word_value(word: &str) -> u32 {
+    word.chars().map(letter_value).sum()
+}
+
+impl fmt::Display for Score {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "  Letters         {:>5}", self.letters)?;
+        writeln!(f, "  Guesses left    {:>5}", self.guesses)?;
+        writeln!(f, "  Time bonus      {:>5}", self.time)?;
+        writeln!(f, "  Hints           {:>5}", format!("-{}", self.hints))?;
+        writeln!(
+            f,
+            "  Difficulty      {:>5}",
+            format!("x{}", self.multiplier)
+        )?;
+        write!(f, "  Score           {:>5}", self.total)
+    }
+}
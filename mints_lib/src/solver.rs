@@ -0,0 +1,169 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::feedback::score_letters;
+use crate::{LetterResult, MAX_LETTERS};
+
+/// How many candidates the entropy of a guess is measured against. Early on
+/// there can be thousands left, so evenly spread ones stand in for the rest.
+pub const SAMPLE_SIZE: usize = 500;
+
+/// Works out which answers are still possible in a Wordle round and which
+/// guesses would narrow them down the most, for `--assist` and `!suggest`.
+#[derive(Clone, Debug)]
+pub struct Solver {
+    answers: Vec<Vec<char>>,
+    guesses: Vec<Vec<char>>,
+}
+
+/// A guess worth making, with how much it's expected to give away.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Suggestion {
+    pub word: String,
+    /// The expected information from the feedback, in bits. Each bit halves the
+    /// possible answers on average.
+    pub entropy: f64,
+    /// Whether the guess could be the answer itself.
+    pub candidate: bool,
+}
+
+impl Solver {
+    /// A solver for answers from `answers` and guesses from `guesses`, both
+    /// normalised (see `Language::normalise`). The answers are always allowed as
+    /// guesses as well.
+    pub fn new(answers: &[impl AsRef<str>], guesses: &[impl AsRef<str>]) -> Self {
+        let answers: Vec<Vec<char>> = answers.iter().map(|w| split(w.as_ref())).collect();
+        let mut guesses: Vec<Vec<char>> = guesses.iter().map(|w| split(w.as_ref())).collect();
+        guesses.extend(answers.iter().cloned());
+        guesses.sort_unstable();
+        guesses.dedup();
+
+        Solver { answers, guesses }
+    }
+
+    /// The answers that would have given exactly this feedback to every guess.
+    pub fn candidates<'a, S: AsRef<str> + 'a>(
+        &self,
+        feedback: impl IntoIterator<Item = (S, &'a Vec<LetterResult>)>,
+    ) -> Vec<String> {
+        let feedback: Vec<(Vec<char>, &Vec<LetterResult>)> = feedback
+            .into_iter()
+            .map(|(guess, results)| (split(guess.as_ref()), results))
+            .collect();
+
+        let mut scratch = [LetterResult::Absent; MAX_LETTERS as usize];
+        self.answers
+            .iter()
+            .filter(|answer| {
+                feedback.iter().all(|(guess, results)| {
+                    let scratch = &mut scratch[..results.len()];
+                    answer.len() == guess.len() && {
+                        score_letters(answer, guess, scratch);
+                        scratch == results.as_slice()
+                    }
+                })
+            })
+            .map(|answer| answer.iter().collect())
+            .collect()
+    }
+
+    /// The `count` guesses expected to tell the `candidates` apart best, most
+    /// informative first. Ties go to guesses that could be the answer, then
+    /// alphabetically. `allowed` filters out guesses that can't be made, e.g. in
+    /// hard mode.
+    pub fn suggest(
+        &self,
+        candidates: &[String],
+        count: usize,
+        allowed: impl Fn(&str) -> bool,
+    ) -> Vec<Suggestion> {
+        let candidates: Vec<Vec<char>> = candidates.iter().map(|c| split(c)).collect();
+        let possible: HashSet<&Vec<char>> = candidates.iter().collect();
+        let Some(length) = candidates.first().map(Vec::len) else {
+            return Vec::new();
+        };
+
+        let sample = sample(&candidates);
+        let mut suggestions: Vec<Suggestion> = self
+            .guesses
+            .iter()
+            .filter(|guess| guess.len() == length)
+            .filter_map(|guess| {
+                let word: String = guess.iter().collect();
+                if !allowed(&word) {
+                    return None;
+                }
+
+                Some(Suggestion {
+                    entropy: expected_information(guess, &sample),
+                    candidate: possible.contains(guess),
+                    word,
+                })
+            })
+            .collect();
+
+        suggestions.sort_by(|a, b| {
+            b.entropy
+                .total_cmp(&a.entropy)
+                .then(b.candidate.cmp(&a.candidate))
+                .then(a.word.cmp(&b.word))
+        });
+        suggestions.truncate(count);
+        suggestions
+    }
+}
+
+/// The information `guess` is expected to give about which of the `candidates`
+/// is the answer, in bits, as ranked by `Solver::suggest`.
+pub fn information(guess: &str, candidates: &[String]) -> f64 {
+    let candidates: Vec<Vec<char>> = candidates.iter().map(|c| split(c)).collect();
+    expected_information(&split(guess), &sample(&candidates))
+}
+
+/// At most `SAMPLE_SIZE` of the candidates, evenly spread.
+fn sample(candidates: &[Vec<char>]) -> Vec<&Vec<char>> {
+    let step = candidates.len().div_ceil(SAMPLE_SIZE).max(1);
+    candidates.iter().step_by(step).collect()
+}
+
+/// The entropy of the feedback `guess` would get across the `sample`.
+fn expected_information(guess: &[char], sample: &[&Vec<char>]) -> f64 {
+    let mut scratch = [LetterResult::Absent; MAX_LETTERS as usize];
+    let mut patterns: HashMap<u32, usize> = HashMap::new();
+    for answer in sample {
+        let scratch = &mut scratch[..guess.len().min(answer.len())];
+        score_letters(answer, guess, scratch);
+        *patterns.entry(pattern(scratch)).or_insert(0) += 1;
+    }
+
+    let counts: Vec<usize> = patterns.into_values().collect();
+    entropy(&counts)
+}
+
+fn split(word: &str) -> Vec<char> {
+    word.chars().collect()
+}
+
+/// The feedback as a single number, each letter a base 3 digit.
+fn pattern(results: &[LetterResult]) -> u32 {
+    results.iter().fold(0, |code, result| {
+        code * 3
+            + match result {
+                LetterResult::Absent => 0,
+                LetterResult::Present => 1,
+                LetterResult::Correct => 2,
+            }
+    })
+}
+
+/// The Shannon entropy, in bits, of outcomes that happened `counts` times each.
+pub fn entropy(counts: &[usize]) -> f64 {
+    let total: usize = counts.iter().sum();
+    counts
+        .iter()
+        .filter(|&&count| count > 0)
+        .map(|&count| {
+            let p = count as f64 / total as f64;
+            p * (1.0 / p).log2()
+        })
+        .sum()
+}
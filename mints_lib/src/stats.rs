@@ -0,0 +1,173 @@
+use std::{collections::BTreeMap, fmt, fs, io, path::PathBuf, time::Duration};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{data_dir, Difficulty, GameResult, Mints, Outcome};
+
+/// The player's statistics across every session, stored as JSON in the mints
+/// data directory. Kept separately for every game, letter count and difficulty.
+#[derive(Serialize, Deserialize, Default, Debug)]
+pub struct Stats {
+    pub modes: Vec<ModeStats>,
+}
+
+/// The statistics for a single game mode.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ModeStats {
+    pub game: Mints,
+    pub letters: i32,
+    pub difficulty: Difficulty,
+    pub played: u32,
+    pub wins: u32,
+    pub current_streak: u32,
+    pub max_streak: u32,
+
+    /// The amount of wins for each number of guesses used.
+    pub distribution: BTreeMap<i32, u32>,
+
+    /// The summed time of every win, for the average solve time.
+    pub total_solve_ms: u64,
+}
+
+impl Stats {
+    /// Where the stats are stored, `$XDG_DATA_HOME/mints/stats.json` on Linux.
+    pub fn path() -> Option<PathBuf> {
+        data_dir().map(|dir| dir.join("stats.json"))
+    }
+
+    /// Loads the stored stats, starting afresh if there are none (or they can't
+    /// be read).
+    pub fn load() -> Self {
+        Self::path()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        let path = Self::path().ok_or(io::ErrorKind::NotFound)?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+
+        fs::write(path, serde_json::to_string_pretty(self)?)
+    }
+
+    /// The stats for the given mode, if it's been played before.
+    pub fn mode(&self, game: Mints, letters: i32, difficulty: &Difficulty) -> Option<&ModeStats> {
+        self.modes
+            .iter()
+            .find(|m| m.game == game && m.letters == letters && &m.difficulty == difficulty)
+    }
+
+    /// Adds a finished round to the stats of its mode. An abandoned round counts
+    /// as a loss.
+    pub fn record(&mut self, result: &GameResult) {
+        let index = match self.modes.iter().position(|m| {
+            m.game == result.game
+                && m.letters == result.letters
+                && m.difficulty == result.difficulty
+        }) {
+            Some(index) => index,
+            None => {
+                self.modes.push(ModeStats::new(
+                    result.game,
+                    result.letters,
+                    result.difficulty.clone(),
+                ));
+                self.modes.len() - 1
+            }
+        };
+
+        let mode = &mut self.modes[index];
+        mode.played += 1;
+
+        if result.outcome == Outcome::Won {
+            mode.wins += 1;
+            mode.current_streak += 1;
+            mode.max_streak = mode.max_streak.max(mode.current_streak);
+            *mode.distribution.entry(result.guesses).or_insert(0) += 1;
+            mode.total_solve_ms += result.elapsed.as_millis() as u64;
+        } else {
+            mode.current_streak = 0;
+        }
+    }
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.modes.is_empty() {
+            return write!(f, "No games played yet!");
+        }
+
+        for (i, mode) in self.modes.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{mode}")?;
+        }
+        Ok(())
+    }
+}
+
+impl ModeStats {
+    fn new(game: Mints, letters: i32, difficulty: Difficulty) -> Self {
+        ModeStats {
+            game,
+            letters,
+            difficulty,
+            played: 0,
+            wins: 0,
+            current_streak: 0,
+            max_streak: 0,
+            distribution: BTreeMap::new(),
+            total_solve_ms: 0,
+        }
+    }
+
+    pub fn win_rate(&self) -> f64 {
+        match self.played {
+            0 => 0.0,
+            played => self.wins as f64 / played as f64 * 100.0,
+        }
+    }
+
+    pub fn average_solve_time(&self) -> Option<Duration> {
+        (self.wins > 0).then(|| Duration::from_millis(self.total_solve_ms / self.wins as u64))
+    }
+}
+
+impl fmt::Display for ModeStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{:?} ({} letters, {:?})",
+            self.game, self.letters, self.difficulty
+        )?;
+        writeln!(
+            f,
+            "  Played: {}  Wins: {} ({:.0}%)  Streak: {}  Max streak: {}",
+            self.played,
+            self.wins,
+            self.win_rate(),
+            self.current_streak,
+            self.max_streak
+        )?;
+
+        if let Some(time) = self.average_solve_time() {
+            let secs = time.as_secs();
+            writeln!(f, "  Average solve time: {}m {:02}s", secs / 60, secs % 60)?;
+        }
+
+        let most = self.distribution.values().max().copied().unwrap_or(0);
+        if most > 0 {
+            writeln!(f, "  Guess distribution:")?;
+            for (guesses, wins) in &self.distribution {
+                let bar = "#".repeat((*wins as usize * 20).div_ceil(most as usize));
+                writeln!(f, "  {guesses:>4} | {bar} {wins}")?;
+            }
+        }
+
+        Ok(())
+    }
+}
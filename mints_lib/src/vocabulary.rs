@@ -0,0 +1,106 @@
+use std::collections::HashSet;
+
+use clap::ValueEnum;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    choose_random_word, frequency_band, load_word_list, Args, Dictionary, Difficulty, Language,
+    ListType, WordList,
+};
+
+/// How picky a game is about which guesses it takes, set with `--strictness` or
+/// per game under `[strictness]` in the config.
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Strictness {
+    /// Only words from the curated lists the answers come from.
+    Strict,
+    /// Any word in the dictionary.
+    Standard,
+    /// Any letters at all.
+    Free,
+}
+
+/// The words a round's answer is picked from.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct AnswerPool {
+    words: Vec<String>,
+}
+
+impl AnswerPool {
+    /// The answers for the given letters from the list, difficulty and rarity in
+    /// `args`, see `load_word_list`.
+    pub fn new(letters: i32, args: &Args) -> Self {
+        let words = match args.word_list {
+            WordList::Builtin(ListType::Frequency) => frequency_band(letters, args.rarity()),
+            _ => load_word_list(letters, &args.difficulty, &args.word_list, args.language),
+        };
+        AnswerPool { words }
+    }
+
+    pub fn choose(&self, rng: &mut impl Rng) -> String {
+        choose_random_word(&self.words, rng)
+    }
+
+    pub fn words(&self) -> &[String] {
+        &self.words
+    }
+}
+
+/// The guesses a round accepts: its curated words, and depending on the
+/// `Strictness`, anything else in the dictionary or any letters at all. Length
+/// is up to the game to check.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AllowedGuesses {
+    pub strictness: Strictness,
+    pub language: Language,
+    curated: HashSet<String>,
+}
+
+impl AllowedGuesses {
+    /// Allows the (case insensitive) `curated` words, and whatever else
+    /// `strictness` lets through in the `language`.
+    pub fn new(
+        strictness: Strictness,
+        language: Language,
+        curated: impl IntoIterator<Item = String>,
+    ) -> Self {
+        AllowedGuesses {
+            strictness,
+            language,
+            curated: curated
+                .into_iter()
+                .map(|word| language.normalise(&word))
+                .collect(),
+        }
+    }
+
+    /// Curates every difficulty of the `letters`-letter list in `args`, so a
+    /// strict round still takes any answer as a guess.
+    pub fn for_list(strictness: Strictness, letters: i32, args: &Args) -> Self {
+        let curated = Difficulty::value_variants()
+            .iter()
+            .flat_map(|diff| load_word_list(letters, diff, &args.word_list, args.language));
+        Self::new(strictness, args.language, curated)
+    }
+
+    /// The curated words, normalised.
+    pub fn curated(&self) -> impl Iterator<Item = &String> {
+        self.curated.iter()
+    }
+
+    /// Whether the word is taken as a guess. The dictionary is English, so in
+    /// other languages a standard round only takes the curated words.
+    pub fn allows(&self, word: &str) -> bool {
+        let word = self.language.normalise(word);
+        match (self.strictness, self.language) {
+            (Strictness::Strict, _) => self.curated.contains(&word),
+            (Strictness::Standard, Language::English) => {
+                self.curated.contains(&word) || Dictionary::get().contains(&word)
+            }
+            (Strictness::Standard, _) => self.curated.contains(&word),
+            (Strictness::Free, language) => language.is_word(&word),
+        }
+    }
+}
@@ -0,0 +1,249 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fmt, fs, io,
+    ops::RangeInclusive,
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::{Arc, Mutex, OnceLock},
+};
+
+use clap::ValueEnum;
+use serde::Deserialize;
+
+use crate::{
+    letter_count, load_word_list, outln, Console, Difficulty, Language, ListType, DEFAULT_LIST,
+};
+
+/// Custom lists already read this session, by path, lengths and language.
+type CustomKey = (PathBuf, RangeInclusive<usize>, Language);
+static CUSTOM_LISTS: OnceLock<Mutex<HashMap<CustomKey, Arc<CustomList>>>> = OnceLock::new();
+
+/// Where a game's answers come from, picked with `--word-list` (or `word_list`
+/// in the config). Either one of the embedded lists by its `ListType` name, a
+/// file of the player's own words, or the name of a file in `lists_dir()`.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "String")]
+pub enum WordList {
+    Builtin(ListType),
+    Custom(PathBuf),
+}
+
+/// The words read from a custom list, along with every entry left out of it.
+#[derive(Clone, Debug, Default)]
+pub struct CustomList {
+    pub words: Vec<String>,
+    pub rejected: Vec<(String, Rejection)>,
+}
+
+/// Why an entry of a custom list was left out.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Rejection {
+    /// Too long, or too short, for the game being played.
+    WrongLength,
+    /// Has something other than the language's letters in it.
+    NonAlpha,
+    /// Already in the list.
+    Duplicate,
+}
+
+impl WordList {
+    /// Where named lists are kept, `$XDG_CONFIG_HOME/mints/lists` on Linux. A list
+    /// saved as `rust.txt` in there is played with `--word-list rust`.
+    pub fn lists_dir() -> Option<PathBuf> {
+        dirs::config_dir().map(|dir| dir.join("mints").join("lists"))
+    }
+
+    /// Reads a custom list, keeping the words (normalised for the `language`)
+    /// with a length in `lengths`. Words can be split over lines or spaces, and
+    /// lines starting with `#` are skipped. Each list is only read and checked
+    /// once a session for the same lengths and language, and shared after that.
+    pub fn load_custom(
+        path: &Path,
+        lengths: RangeInclusive<usize>,
+        language: Language,
+    ) -> io::Result<Arc<CustomList>> {
+        let lists = CUSTOM_LISTS.get_or_init(Default::default);
+        let key = (path.to_path_buf(), lengths.clone(), language);
+        if let Some(list) = lists.lock().unwrap().get(&key) {
+            return Ok(Arc::clone(list));
+        }
+
+        let list = Arc::new(Self::read_custom(path, lengths, language)?);
+        lists.lock().unwrap().insert(key, Arc::clone(&list));
+        Ok(list)
+    }
+
+    fn read_custom(
+        path: &Path,
+        lengths: RangeInclusive<usize>,
+        language: Language,
+    ) -> io::Result<CustomList> {
+        let text = fs::read_to_string(path)
+            .map_err(|e| io::Error::new(e.kind(), format!("{}: {e}", path.display())))?;
+        let mut list = CustomList::default();
+        let mut seen = HashSet::new();
+
+        for line in text
+            .lines()
+            .filter(|line| !line.trim_start().starts_with('#'))
+        {
+            for entry in line.split_whitespace() {
+                let word = language.normalise(entry);
+                let rejection = if !language.is_word(&word) {
+                    Some(Rejection::NonAlpha)
+                } else if !lengths.contains(&letter_count(&word)) {
+                    Some(Rejection::WrongLength)
+                } else if !seen.insert(word.clone()) {
+                    Some(Rejection::Duplicate)
+                } else {
+                    None
+                };
+
+                match rejection {
+                    Some(reason) => list.rejected.push((entry.to_string(), reason)),
+                    None => list.words.push(word),
+                }
+            }
+        }
+
+        // Sorted so that a seeded RNG always picks the same word.
+        list.words.sort_unstable();
+        Ok(list)
+    }
+
+    /// Checks a list has words for the game before it's played, telling the
+    /// player about anything left out of a custom one.
+    pub fn check(
+        &self,
+        io: &mut Console,
+        lengths: RangeInclusive<usize>,
+        language: Language,
+    ) -> io::Result<()> {
+        let path = match self {
+            WordList::Builtin(list) => return check_builtin(*list, lengths),
+            WordList::Custom(path) => path,
+        };
+
+        let list = Self::load_custom(path, lengths.clone(), language)?;
+        if !list.rejected.is_empty() {
+            outln!(
+                io,
+                "Left {} entries out of {}:",
+                list.rejected.len(),
+                path.display()
+            );
+            for reason in [
+                Rejection::WrongLength,
+                Rejection::NonAlpha,
+                Rejection::Duplicate,
+            ] {
+                let entries: Vec<&str> = list
+                    .rejected
+                    .iter()
+                    .filter(|(_, r)| *r == reason)
+                    .map(|(entry, _)| entry.as_str())
+                    .collect();
+                if !entries.is_empty() {
+                    outln!(io, "  {reason}: {}", entries.join(", "));
+                }
+            }
+        }
+
+        if list.words.is_empty() {
+            return Err(no_words(&path.display().to_string(), &lengths));
+        }
+
+        Ok(())
+    }
+}
+
+/// The GPT lists only have 4 to 8 letter words, longer or shorter games need
+/// the frequency list.
+fn check_builtin(list: ListType, lengths: RangeInclusive<usize>) -> io::Result<()> {
+    let has_words = lengths.clone().any(|letters| {
+        let letters = letters as i32;
+        Difficulty::value_variants().iter().any(|diff| {
+            !load_word_list(letters, diff, &WordList::Builtin(list), Language::English).is_empty()
+        })
+    });
+
+    match has_words {
+        true => Ok(()),
+        false => Err(no_words(
+            &format!("The {} list", WordList::Builtin(list)),
+            &lengths,
+        )),
+    }
+}
+
+fn no_words(list: &str, lengths: &RangeInclusive<usize>) -> io::Error {
+    let (min, max) = (lengths.start(), lengths.end());
+    let letters = match min == max {
+        true => format!("{min}"),
+        false => format!("{min}-{max}"),
+    };
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("{list} has no {letters} letter words to play!"),
+    )
+}
+
+impl Default for WordList {
+    fn default() -> Self {
+        WordList::Builtin(DEFAULT_LIST)
+    }
+}
+
+impl FromStr for WordList {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Ok(list) = ListType::from_str(s, true) {
+            return Ok(WordList::Builtin(list));
+        }
+
+        let path = PathBuf::from(s);
+        if path.is_file() {
+            return Ok(WordList::Custom(path));
+        }
+
+        match Self::lists_dir().map(|dir| dir.join(format!("{s}.txt"))) {
+            Some(path) if path.is_file() => Ok(WordList::Custom(path)),
+            Some(path) => Err(format!(
+                "{s} isn't frequency, gpt, webster, a file or a list in {}",
+                path.parent().unwrap_or(&path).display()
+            )),
+            None => Err(format!("{s} isn't frequency, gpt, webster or a file")),
+        }
+    }
+}
+
+impl TryFrom<String> for WordList {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl fmt::Display for WordList {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WordList::Builtin(list) => match list.to_possible_value() {
+                Some(value) => write!(f, "{}", value.get_name()),
+                None => write!(f, "{list:?}"),
+            },
+            WordList::Custom(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+impl fmt::Display for Rejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Rejection::WrongLength => write!(f, "Wrong length"),
+            Rejection::NonAlpha => write!(f, "Not just letters"),
+            Rejection::Duplicate => write!(f, "Duplicates"),
+        }
+    }
+}
@@ -0,0 +1,112 @@
+//! Wordle's hard mode rules from `hard_mode_violation`.
+
+use mints_lib::{hard_mode_violation, score_guess};
+
+/// Checks `guess` in a round for `answer` after the `previous` guesses.
+fn check(answer: &str, previous: &[&str], guess: &str) -> Option<String> {
+    let previous = previous
+        .iter()
+        .map(|previous| (*previous, score_guess(answer, previous)));
+    hard_mode_violation(previous, guess)
+}
+
+#[test]
+fn anything_goes_before_the_first_guess() {
+    assert_eq!(check("CRANE", &[], "PILOT"), None);
+}
+
+#[test]
+fn keeping_every_clue_is_fine() {
+    // C and R in place, N somewhere else.
+    assert_eq!(check("CRANE", &["CRONY"], "CRANE"), None);
+    assert_eq!(check("CRANE", &["CRONY"], "CRWNE"), None);
+}
+
+#[test]
+fn green_letter_moved() {
+    assert_eq!(
+        check("CRANE", &["CRONE"], "CARNE"),
+        Some("The 2nd letter has to be R".to_string())
+    );
+    assert_eq!(
+        check("CRANE", &["PLANE"], "ANGLE"),
+        Some("The 3rd letter has to be A".to_string())
+    );
+}
+
+#[test]
+fn green_letter_dropped() {
+    assert_eq!(
+        check("CRANE", &["CRONE"], "BRAKE"),
+        Some("The 1st letter has to be C".to_string())
+    );
+}
+
+#[test]
+fn yellow_letter_dropped() {
+    // N and E are both in CRANE, but not where NOTES has them.
+    assert_eq!(
+        check("CRANE", &["NOTES"], "ABODE"),
+        Some("ABODE has to use N".to_string())
+    );
+    assert_eq!(
+        check("CRANE", &["NOTES"], "NAILS"),
+        Some("NAILS has to use E".to_string())
+    );
+}
+
+#[test]
+fn yellow_letter_moved_is_fine() {
+    assert_eq!(check("CRANE", &["NOTES"], "ENACT"), None);
+}
+
+#[test]
+fn repeated_letter_one_yellow_one_grey() {
+    // SPEED is --Y-- against CRANE, so the answer has just the one E.
+    assert_eq!(check("CRANE", &["SPEED"], "ELBOW"), None);
+    assert_eq!(
+        check("CRANE", &["SPEED"], "TOAST"),
+        Some("TOAST has to use E".to_string())
+    );
+}
+
+#[test]
+fn repeated_letter_both_yellow() {
+    // HELLO is --YY- against LLAMA, so both Ls have to be used.
+    assert_eq!(check("LLAMA", &["HELLO"], "ALLAY"), None);
+    assert_eq!(
+        check("LLAMA", &["HELLO"], "LEARN"),
+        Some("LEARN has to use L 2 times".to_string())
+    );
+}
+
+#[test]
+fn repeated_letter_one_green_one_yellow() {
+    // EERIE is ---YG against ABIDE, so it only needs the I and the green E...
+    assert_eq!(check("ABIDE", &["EERIE"], "IMAGE"), None);
+    // ...but against EMBER it's GYY--, so a second E has to go somewhere.
+    assert_eq!(
+        check("EMBER", &["EERIE"], "EVICT"),
+        Some("EVICT has to use E 2 times".to_string())
+    );
+}
+
+#[test]
+fn every_previous_guess_counts() {
+    assert_eq!(
+        check("CRANE", &["CRONY", "SPEED"], "CRANK"),
+        Some("CRANK has to use E".to_string())
+    );
+}
+
+#[test]
+fn ordinals_past_the_third() {
+    assert_eq!(
+        check("CRANE", &["PLANE"], "PLANT"),
+        Some("The 5th letter has to be E".to_string())
+    );
+    assert_eq!(
+        check("SPELLINGBEES", &["SPELLINGBEES"], "SPELLINGBEAS"),
+        Some("The 11th letter has to be E".to_string())
+    );
+}
@@ -0,0 +1,73 @@
+//! Folding words into each language's alphabet with `Language::normalise`.
+
+use mints_lib::{letter_count, Language};
+
+#[test]
+fn uppercases() {
+    assert_eq!(Language::English.normalise("crane"), "CRANE");
+    assert_eq!(Language::English.normalise("CrAnE"), "CRANE");
+}
+
+#[test]
+fn drops_accents_outside_the_alphabet() {
+    assert_eq!(Language::English.normalise("café"), "CAFE");
+    assert_eq!(Language::French.normalise("élève"), "ELEVE");
+    assert_eq!(Language::Spanish.normalise("canción"), "CANCION");
+    assert_eq!(Language::German.normalise("café"), "CAFE");
+}
+
+#[test]
+fn keeps_letters_in_the_alphabet() {
+    assert_eq!(Language::Spanish.normalise("año"), "AÑO");
+    assert_eq!(Language::German.normalise("müde"), "MÜDE");
+    assert_eq!(Language::German.normalise("Äpfel"), "ÄPFEL");
+    // Not letters of their own outside German and Spanish.
+    assert_eq!(Language::English.normalise("año"), "ANO");
+    assert_eq!(Language::French.normalise("müde"), "MUDE");
+}
+
+#[test]
+fn spells_out_ligatures() {
+    assert_eq!(Language::German.normalise("straße"), "STRASSE");
+    assert_eq!(Language::French.normalise("cœur"), "COEUR");
+    assert_eq!(Language::French.normalise("ex æquo"), "EX AEQUO");
+}
+
+#[test]
+fn composes_decomposed_letters() {
+    // N followed by a combining tilde is the one letter Ñ.
+    let word = Language::Spanish.normalise("an\u{303}o");
+    assert_eq!(word, "AÑO");
+    assert_eq!(word.chars().count(), 3);
+    assert_eq!(Language::English.normalise("an\u{303}o"), "ANO");
+}
+
+#[test]
+fn leaves_anything_else_alone() {
+    assert_eq!(Language::English.normalise("can't stop"), "CAN'T STOP");
+    assert_eq!(Language::English.normalise("cr4ne!"), "CR4NE!");
+    assert_eq!(Language::English.normalise(""), "");
+}
+
+#[test]
+fn normalised_words_are_words() {
+    for language in [
+        Language::English,
+        Language::Spanish,
+        Language::German,
+        Language::French,
+    ] {
+        for word in ["straße", "cœur", "año", "müde", "élève"] {
+            let word = language.normalise(word);
+            assert!(language.is_word(&word), "{language} {word}");
+        }
+    }
+    assert!(!Language::English.is_word("CAN'T"));
+    assert!(!Language::English.is_word(""));
+}
+
+#[test]
+fn letters_are_counted_as_seen() {
+    assert_eq!(letter_count("AÑO"), 3);
+    assert_eq!(letter_count("an\u{303}o"), 3);
+}
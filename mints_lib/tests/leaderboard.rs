@@ -0,0 +1,154 @@
+//! Ranking won rounds on their boards with `Leaderboard::record`.
+
+use std::time::Duration;
+
+use mints_lib::{
+    player, Difficulty, GameResult, Leaderboard, Mints, Outcome, Rules, Score, BOARD_SIZE,
+};
+
+fn rules(seed: Option<u64>) -> Rules {
+    Rules {
+        seed,
+        guesses: Some(6),
+        hard_mode: false,
+        word_list: "frequency".to_string(),
+        rarity: None,
+    }
+}
+
+/// A five letter Wordle round with the given outcome and total score.
+fn round(outcome: Outcome, total: u32) -> GameResult {
+    let mut score = Score::new(&["CRANE"], &Difficulty::Medium, 0);
+    score.total = total;
+    GameResult {
+        game: Mints::Wordle,
+        letters: 5,
+        difficulty: Difficulty::Medium,
+        outcome,
+        answer: "CRANE".to_string(),
+        guesses: 3,
+        history: Vec::new(),
+        hints_used: 1,
+        assisted: false,
+        score,
+        puzzle: None,
+        elapsed: Duration::from_secs(42),
+    }
+}
+
+fn scores(leaderboard: &Leaderboard) -> Vec<u32> {
+    leaderboard.boards[0]
+        .entries
+        .iter()
+        .map(|e| e.score)
+        .collect()
+}
+
+#[test]
+fn best_score_first() {
+    let mut leaderboard = Leaderboard::default();
+    assert_eq!(
+        leaderboard.record(&round(Outcome::Won, 50), &rules(None)),
+        Some(1)
+    );
+    assert_eq!(
+        leaderboard.record(&round(Outcome::Won, 80), &rules(None)),
+        Some(1)
+    );
+    assert_eq!(
+        leaderboard.record(&round(Outcome::Won, 60), &rules(None)),
+        Some(2)
+    );
+    assert_eq!(scores(&leaderboard), [80, 60, 50]);
+}
+
+#[test]
+fn ties_go_to_whoever_was_first() {
+    let mut leaderboard = Leaderboard::default();
+    leaderboard.record(&round(Outcome::Won, 50), &rules(None));
+    assert_eq!(
+        leaderboard.record(&round(Outcome::Won, 50), &rules(None)),
+        Some(2)
+    );
+    assert_eq!(
+        leaderboard.record(&round(Outcome::Won, 70), &rules(None)),
+        Some(1)
+    );
+}
+
+#[test]
+fn only_the_best_are_kept() {
+    let mut leaderboard = Leaderboard::default();
+    for place in 1..=BOARD_SIZE {
+        let recorded = leaderboard.record(&round(Outcome::Won, 100), &rules(None));
+        assert_eq!(recorded, Some(place));
+    }
+
+    // Too low (or only as good) to make a full board.
+    assert_eq!(
+        leaderboard.record(&round(Outcome::Won, 50), &rules(None)),
+        None
+    );
+    assert_eq!(
+        leaderboard.record(&round(Outcome::Won, 100), &rules(None)),
+        None
+    );
+
+    assert_eq!(
+        leaderboard.record(&round(Outcome::Won, 200), &rules(None)),
+        Some(1)
+    );
+    assert_eq!(leaderboard.boards[0].entries.len(), BOARD_SIZE);
+    assert_eq!(scores(&leaderboard)[..2], [200, 100]);
+}
+
+#[test]
+fn only_won_rounds_count() {
+    let mut leaderboard = Leaderboard::default();
+    assert_eq!(
+        leaderboard.record(&round(Outcome::Lost, 50), &rules(None)),
+        None
+    );
+    assert_eq!(
+        leaderboard.record(&round(Outcome::Abandoned, 50), &rules(None)),
+        None
+    );
+    assert!(leaderboard.boards.is_empty());
+}
+
+#[test]
+fn assisted_rounds_dont_count() {
+    let mut leaderboard = Leaderboard::default();
+    let mut assisted = round(Outcome::Won, 500);
+    assisted.assisted = true;
+    assert_eq!(leaderboard.record(&assisted, &rules(None)), None);
+    assert!(leaderboard.boards.is_empty());
+}
+
+#[test]
+fn entries_are_the_round() {
+    let mut leaderboard = Leaderboard::default();
+    leaderboard.record(&round(Outcome::Won, 50), &rules(None));
+
+    let entry = &leaderboard.boards[0].entries[0];
+    assert_eq!(entry.player, player());
+    assert_eq!(entry.guesses, 3);
+    assert_eq!(entry.hints_used, 1);
+    assert_eq!(entry.elapsed_ms, 42_000);
+}
+
+#[test]
+fn boards_by_rules_and_puzzle() {
+    let mut leaderboard = Leaderboard::default();
+    leaderboard.record(&round(Outcome::Won, 50), &rules(None));
+    leaderboard.record(&round(Outcome::Won, 50), &rules(Some(7)));
+    leaderboard.record(&round(Outcome::Won, 50), &rules(Some(7)));
+
+    let mut daily = round(Outcome::Won, 50);
+    daily.puzzle = Some(12);
+    leaderboard.record(&daily, &rules(Some(7)));
+
+    let sizes: Vec<usize> = leaderboard.boards.iter().map(|b| b.entries.len()).collect();
+    assert_eq!(sizes, [1, 2, 1]);
+    assert_eq!(leaderboard.boards(None, true).len(), 1);
+}
@@ -0,0 +1,143 @@
+//! Wordle feedback from `score_guess`, focusing on repeated letters.
+
+use mints_lib::{marks, score_guess, LetterResult};
+
+/// The feedback as `G`/`Y`/`-` marks, which keeps the cases readable.
+fn score(answer: &str, guess: &str) -> String {
+    marks(&score_guess(answer, guess))
+}
+
+#[test]
+fn exact_match_is_all_correct() {
+    assert_eq!(score("CRANE", "CRANE"), "GGGGG");
+}
+
+#[test]
+fn no_shared_letters_is_all_absent() {
+    assert_eq!(score("CRANE", "PILOT"), "-----");
+}
+
+#[test]
+fn letters_elsewhere_are_present() {
+    assert_eq!(score("CRANE", "NACRE"), "YYYYG");
+    assert_eq!(score("CRANE", "TRACE"), "-GGYG");
+}
+
+#[test]
+fn repeated_guess_letter_once_in_answer_in_place() {
+    // Only the E in place lights up, the answer has no second E.
+    assert_eq!(score("CRANE", "EERIE"), "--Y-G");
+    assert_eq!(score("ABBEY", "KEEPS"), "-Y---");
+}
+
+#[test]
+fn repeated_guess_letter_once_in_answer_out_of_place() {
+    // The first of the two gets the yellow.
+    assert_eq!(score("CRANE", "SPEED"), "--Y--");
+    assert_eq!(score("WORLD", "LLAMA"), "Y----");
+}
+
+#[test]
+fn correct_letter_takes_priority_over_earlier_present() {
+    // The last E is in place, so the earlier ones have no E left to be yellow for.
+    assert_eq!(score("ABIDE", "EERIE"), "---YG");
+    // The last L is in place, leaving the answer's other L for the first.
+    assert_eq!(score("SPELL", "LEVEL"), "YY--G");
+}
+
+#[test]
+fn repeated_letters_in_both() {
+    assert_eq!(score("ABBEY", "BABES"), "YYGG-");
+    assert_eq!(score("ABBEY", "BOBBY"), "Y-G-G");
+    assert_eq!(score("HELLO", "LLAMA"), "YY---");
+    assert_eq!(score("LLAMA", "HELLO"), "--YY-");
+    assert_eq!(score("EERIE", "EEEEE"), "GG--G");
+}
+
+#[test]
+fn more_copies_in_answer_than_guess() {
+    assert_eq!(score("EERIE", "CRANE"), "-Y--G");
+    assert_eq!(score("MAMMA", "AMAZE"), "YYY--");
+}
+
+#[test]
+fn answer_letter_used_up_by_correct_match() {
+    assert_eq!(score("SASSY", "ASSES"), "YYG-Y");
+    assert_eq!(score("FLOOR", "ORBIT"), "YY---");
+}
+
+#[test]
+fn non_ascii_letters_compare_as_single_letters() {
+    assert_eq!(score("AÑEJO", "ÑANDU"), "YY---");
+    assert_eq!(score("ÄPFEL", "APFEL"), "-GGGG");
+}
+
+#[test]
+fn shorter_guess_is_scored_as_far_as_it_goes() {
+    assert_eq!(score_guess("CRANE", "CRA").len(), 3);
+    assert_eq!(score("CRANE", "CRA"), "GGG");
+}
+
+#[test]
+fn marks_map_each_result() {
+    let results = [
+        LetterResult::Correct,
+        LetterResult::Present,
+        LetterResult::Absent,
+    ];
+    assert_eq!(marks(&results), "GY-");
+}
+
+/// Every pair of 4-letter words over a 3-letter alphabet, which covers every
+/// way letters can repeat, checked against the rules the feedback has to keep.
+#[test]
+fn every_small_word_pair_keeps_the_rules() {
+    let words: Vec<String> = (0..81)
+        .map(|mut n| {
+            (0..4)
+                .map(|_| {
+                    let c = (b'A' + (n % 3) as u8) as char;
+                    n /= 3;
+                    c
+                })
+                .collect()
+        })
+        .collect();
+
+    for answer in &words {
+        for guess in &words {
+            let results = score_guess(answer, guess);
+            let answer: Vec<char> = answer.chars().collect();
+            let guess: Vec<char> = guess.chars().collect();
+
+            for (i, result) in results.iter().enumerate() {
+                // Green exactly where the letters match.
+                assert_eq!(
+                    *result == LetterResult::Correct,
+                    guess[i] == answer[i],
+                    "{answer:?} {guess:?}"
+                );
+            }
+
+            for letter in ['A', 'B', 'C'] {
+                let in_answer = answer.iter().filter(|&&c| c == letter).count();
+                let in_guess = guess.iter().filter(|&&c| c == letter).count();
+                let lit: Vec<usize> = (0..4)
+                    .filter(|&i| guess[i] == letter && results[i] != LetterResult::Absent)
+                    .collect();
+
+                // A letter lights up as often as both words have it.
+                assert_eq!(lit.len(), in_answer.min(in_guess), "{answer:?} {guess:?}");
+
+                // Yellows go to the earliest copies that aren't green.
+                let present: Vec<usize> = (0..4)
+                    .filter(|&i| guess[i] == letter && results[i] == LetterResult::Present)
+                    .collect();
+                let candidates: Vec<usize> = (0..4)
+                    .filter(|&i| guess[i] == letter && results[i] != LetterResult::Correct)
+                    .collect();
+                assert_eq!(present, candidates[..present.len()], "{answer:?} {guess:?}");
+            }
+        }
+    }
+}
@@ -0,0 +1,111 @@
+//! The Wordle solver behind `--assist` and `!suggest`.
+
+use mints_lib::{entropy, information, score_guess, Solver};
+
+const ANSWERS: [&str; 3] = ["BATCH", "CATCH", "HATCH"];
+
+fn close(a: f64, b: f64) -> bool {
+    (a - b).abs() < 1e-9
+}
+
+#[test]
+fn entropy_in_bits() {
+    assert!(close(entropy(&[1, 1]), 1.0));
+    assert!(close(entropy(&[1, 1, 1, 1]), 2.0));
+    assert!(close(entropy(&[2, 1, 1]), 1.5));
+    assert!(close(entropy(&[3, 0, 1]), entropy(&[3, 1])));
+}
+
+#[test]
+fn entropy_of_a_sure_thing_is_nothing() {
+    assert_eq!(entropy(&[5]), 0.0);
+    assert_eq!(entropy(&[]), 0.0);
+}
+
+#[test]
+fn every_answer_is_a_candidate_at_first() {
+    let solver = Solver::new(&ANSWERS, &["CHUBS"]);
+    let feedback: [(&str, &Vec<_>); 0] = [];
+    assert_eq!(solver.candidates(feedback), ANSWERS);
+}
+
+#[test]
+fn candidates_fit_every_guess() {
+    let solver = Solver::new(
+        &["CRANE", "CRATE", "TRACE", "SLATE", "CRANK"],
+        &[] as &[&str],
+    );
+
+    let crane = score_guess("CRATE", "CRANE");
+    assert_eq!(solver.candidates([("CRANE", &crane)]), ["CRATE"]);
+
+    // NOISY shares no letters with CRATE, which only TRACE also has none of.
+    let noisy = score_guess("CRATE", "NOISY");
+    assert_eq!(solver.candidates([("NOISY", &noisy)]), ["CRATE", "TRACE"]);
+    assert_eq!(
+        solver.candidates([("NOISY", &noisy), ("CRANE", &crane)]),
+        ["CRATE"]
+    );
+}
+
+#[test]
+fn candidates_have_the_guess_length() {
+    let solver = Solver::new(&["CRANE", "CRANES"], &[] as &[&str]);
+    let results = score_guess("CRANE", "CRANE");
+    assert_eq!(solver.candidates([("CRANE", &results)]), ["CRANE"]);
+}
+
+#[test]
+fn best_guess_tells_every_candidate_apart() {
+    // CHUBS gets different feedback from each answer, while each answer can only
+    // tell itself apart from the other two.
+    let solver = Solver::new(&ANSWERS, &["CHUBS", "BOTCH"]);
+    let candidates = ANSWERS.map(String::from);
+    let suggestions = solver.suggest(&candidates, 5, |_| true);
+
+    let words: Vec<&str> = suggestions.iter().map(|s| s.word.as_str()).collect();
+    assert_eq!(words, ["CHUBS", "BATCH", "CATCH", "HATCH", "BOTCH"]);
+
+    assert!(close(suggestions[0].entropy, 3f64.log2()));
+    assert!(!suggestions[0].candidate);
+    assert!(close(suggestions[1].entropy, entropy(&[1, 2])));
+    assert!(suggestions[1].candidate);
+}
+
+#[test]
+fn ties_go_to_possible_answers() {
+    // BOTCH splits them the same as any of the answers, but can't win.
+    let solver = Solver::new(&ANSWERS, &["BOTCH"]);
+    let candidates = ANSWERS.map(String::from);
+    let suggestions = solver.suggest(&candidates, 5, |_| true);
+
+    assert!(close(suggestions[0].entropy, suggestions[3].entropy));
+    assert_eq!(suggestions[3].word, "BOTCH");
+    assert!(suggestions[..3].iter().all(|s| s.candidate));
+}
+
+#[test]
+fn suggestions_are_limited_and_filtered() {
+    let solver = Solver::new(&ANSWERS, &["CHUBS", "BOTCH"]);
+    let candidates = ANSWERS.map(String::from);
+
+    assert_eq!(solver.suggest(&candidates, 2, |_| true).len(), 2);
+
+    let suggestions = solver.suggest(&candidates, 5, |guess| guess != "CHUBS");
+    assert_eq!(suggestions[0].word, "BATCH");
+    assert!(suggestions.iter().all(|s| s.word != "CHUBS"));
+}
+
+#[test]
+fn nothing_to_suggest_without_candidates() {
+    let solver = Solver::new(&ANSWERS, &["CHUBS"]);
+    assert!(solver.suggest(&[], 5, |_| true).is_empty());
+}
+
+#[test]
+fn information_matches_the_ranking() {
+    let candidates = ANSWERS.map(String::from);
+    assert!(close(information("CHUBS", &candidates), 3f64.log2()));
+    assert!(close(information("BATCH", &candidates), entropy(&[1, 2])));
+    assert_eq!(information("BATCH", &candidates[..1]), 0.0);
+}
@@ -0,0 +1,107 @@
+//! Reading custom word lists with `WordList::load_custom`.
+
+use std::{fs, path::PathBuf, sync::Arc};
+
+use mints_lib::{Language, Rejection, WordList};
+
+/// Writes `contents` to a list file of its own in the temp directory.
+fn list(name: &str, contents: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("mints-tests-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let path = dir.join(format!("{name}.txt"));
+    fs::write(&path, contents).unwrap();
+    path
+}
+
+fn rejected(name: &str, contents: &str, language: Language) -> Vec<(String, Rejection)> {
+    WordList::load_custom(&list(name, contents), 5..=5, language)
+        .unwrap()
+        .rejected
+        .clone()
+}
+
+#[test]
+fn keeps_words_normalised_and_sorted() {
+    let path = list("kept", "crane slate\n  Adieu\n");
+    let list = WordList::load_custom(&path, 5..=5, Language::English).unwrap();
+    assert_eq!(list.words, ["ADIEU", "CRANE", "SLATE"]);
+    assert!(list.rejected.is_empty());
+}
+
+#[test]
+fn skips_comment_lines() {
+    let path = list("comments", "# crane slate\ntrain\n  # brick\n");
+    let list = WordList::load_custom(&path, 5..=5, Language::English).unwrap();
+    assert_eq!(list.words, ["TRAIN"]);
+    assert!(list.rejected.is_empty());
+}
+
+#[test]
+fn rejects_the_wrong_length() {
+    assert_eq!(
+        rejected("length", "cat crane kitchen", Language::English),
+        [
+            ("cat".to_string(), Rejection::WrongLength),
+            ("kitchen".to_string(), Rejection::WrongLength)
+        ]
+    );
+}
+
+#[test]
+fn rejects_anything_but_letters() {
+    assert_eq!(
+        rejected("letters", "crane cr4ne can't", Language::English),
+        [
+            ("cr4ne".to_string(), Rejection::NonAlpha),
+            ("can't".to_string(), Rejection::NonAlpha)
+        ]
+    );
+}
+
+#[test]
+fn rejects_duplicates_after_normalising() {
+    assert_eq!(
+        rejected("duplicates", "crane CRANE Crane", Language::English),
+        [
+            ("CRANE".to_string(), Rejection::Duplicate),
+            ("Crane".to_string(), Rejection::Duplicate)
+        ]
+    );
+}
+
+#[test]
+fn letters_count_for_the_language() {
+    // Ñ is a letter in Spanish, and folds into N in English.
+    let path = list("spanish", "añejo anejo");
+    let spanish = WordList::load_custom(&path, 5..=5, Language::Spanish).unwrap();
+    assert_eq!(spanish.words, ["ANEJO", "AÑEJO"]);
+
+    let english = WordList::load_custom(&path, 5..=5, Language::English).unwrap();
+    assert_eq!(english.words, ["ANEJO"]);
+    assert_eq!(
+        english.rejected,
+        [("anejo".to_string(), Rejection::Duplicate)]
+    );
+}
+
+#[test]
+fn missing_list_names_the_path() {
+    let path = std::env::temp_dir().join("mints-tests-no-such-list.txt");
+    let error = WordList::load_custom(&path, 5..=5, Language::English).unwrap_err();
+    assert!(error.to_string().contains("mints-tests-no-such-list.txt"));
+}
+
+#[test]
+fn list_is_only_read_once() {
+    let path = list("cached", "crane");
+    let first = WordList::load_custom(&path, 5..=5, Language::English).unwrap();
+
+    fs::write(&path, "slate").unwrap();
+    let second = WordList::load_custom(&path, 5..=5, Language::English).unwrap();
+    assert!(Arc::ptr_eq(&first, &second));
+    assert_eq!(second.words, ["CRANE"]);
+
+    // Other lengths are a list of their own.
+    let other = WordList::load_custom(&path, 4..=5, Language::English).unwrap();
+    assert_eq!(other.words, ["SLATE"]);
+}